@@ -1,7 +1,13 @@
 use std::collections::BinaryHeap;
 
 use divan::Bencher;
-use meldable_heaps::{LeftistHeap, PairingHeap, SkewHeap};
+use meldable_heaps::{
+    AuxiliaryTwoPass, BackToFront, BottomUpSkewHeap, CalendarQueue, DoubleEndedPairingHeap,
+    FibonacciHeap, FrontToBack, HollowHeap, LeftistHeap, MeldableHeap, Multipass, NoClock,
+    PairingHeap, PairingStrategy, RadixHeap, RadixKey, RandomizedMeldableHeap, RankPairingHeap,
+    RankRule, Recording, SkewBinomialHeap, SkewHeap, SmoothHeap, Timestamped, Trace, TwoPass,
+    Type1Rank, WeightBiasedLeftistHeap,
+};
 use mimalloc::MiMalloc;
 use rand::{rngs::SmallRng, Rng, SeedableRng};
 
@@ -28,8 +34,8 @@ impl<T: Ord> Heap<T> for BinaryHeap<T> {
 }
 
 #[rustfmt::skip]
-impl<T: Ord> Heap<T> for PairingHeap<T> {
-    fn new() -> Self { Self::new() }
+impl<T: Ord, S: PairingStrategy> Heap<T> for PairingHeap<T, S> {
+    fn new() -> Self { Self::default() }
     fn push(&mut self, value: T) { self.push(value) }
     fn pop(&mut self) -> Option<T> { self.pop() }
     fn is_empty(&self) -> bool { self.is_empty() }
@@ -51,51 +57,262 @@ impl<T: Ord> Heap<T> for LeftistHeap<T> {
     fn is_empty(&self) -> bool { self.is_empty() }
 }
 
+#[rustfmt::skip]
+impl<T: Ord, R: RankRule> Heap<T> for RankPairingHeap<T, R> {
+    fn new() -> Self { Self::default() }
+    fn push(&mut self, value: T) { self.push(value); }
+    fn pop(&mut self) -> Option<T> { self.pop() }
+    fn is_empty(&self) -> bool { self.is_empty() }
+}
+
+#[rustfmt::skip]
+impl<T: Ord> Heap<T> for HollowHeap<T> {
+    fn new() -> Self { Self::new() }
+    fn push(&mut self, value: T) { self.push(value); }
+    fn pop(&mut self) -> Option<T> { self.pop() }
+    fn is_empty(&self) -> bool { self.is_empty() }
+}
+
+#[rustfmt::skip]
+impl<T: Ord> Heap<T> for SkewBinomialHeap<T> {
+    fn new() -> Self { Self::new() }
+    fn push(&mut self, value: T) { self.push(value) }
+    fn pop(&mut self) -> Option<T> { self.pop() }
+    fn is_empty(&self) -> bool { self.is_empty() }
+}
+
+#[rustfmt::skip]
+impl<T: Ord> Heap<T> for WeightBiasedLeftistHeap<T> {
+    fn new() -> Self { Self::new() }
+    fn push(&mut self, value: T) { self.push(value) }
+    fn pop(&mut self) -> Option<T> { self.pop() }
+    fn is_empty(&self) -> bool { self.is_empty() }
+}
+
+#[rustfmt::skip]
+impl<T: Ord> Heap<T> for RandomizedMeldableHeap<T> {
+    fn new() -> Self { Self::new() }
+    fn push(&mut self, value: T) { self.push(value) }
+    fn pop(&mut self) -> Option<T> { self.pop() }
+    fn is_empty(&self) -> bool { self.is_empty() }
+}
+
+#[rustfmt::skip]
+impl<T: Ord> Heap<T> for BottomUpSkewHeap<T> {
+    fn new() -> Self { Self::new() }
+    fn push(&mut self, value: T) { self.push(value) }
+    fn pop(&mut self) -> Option<T> { self.pop() }
+    fn is_empty(&self) -> bool { self.is_empty() }
+}
+
+#[rustfmt::skip]
+impl<T: Ord> Heap<T> for SmoothHeap<T> {
+    fn new() -> Self { Self::new() }
+    fn push(&mut self, value: T) { self.push(value) }
+    fn pop(&mut self) -> Option<T> { self.pop() }
+    fn is_empty(&self) -> bool { self.is_empty() }
+}
+
+#[rustfmt::skip]
+impl<T: Ord> Heap<T> for FibonacciHeap<T> {
+    fn new() -> Self { Self::new() }
+    fn push(&mut self, value: T) { self.push(value); }
+    fn pop(&mut self) -> Option<T> { self.pop() }
+    fn is_empty(&self) -> bool { self.is_empty() }
+}
+
+#[rustfmt::skip]
+impl<T: Ord> Heap<T> for DoubleEndedPairingHeap<T> {
+    fn new() -> Self { Self::new() }
+    fn push(&mut self, value: T) { self.push(value) }
+    fn pop(&mut self) -> Option<T> { self.pop_min() }
+    fn is_empty(&self) -> bool { self.is_empty() }
+}
+
+#[rustfmt::skip]
+impl<T: Timestamped> Heap<T> for CalendarQueue<T> {
+    fn new() -> Self { Self::new() }
+    fn push(&mut self, value: T) { self.push(value) }
+    fn pop(&mut self) -> Option<T> { self.pop() }
+    fn is_empty(&self) -> bool { self.is_empty() }
+}
+
+#[rustfmt::skip]
+impl<K: RadixKey, V: Ord> Heap<(K, V)> for RadixHeap<K, V> {
+    fn new() -> Self { Self::new() }
+    fn push(&mut self, value: (K, V)) { MeldableHeap::push(self, value) }
+    fn pop(&mut self) -> Option<(K, V)> { self.pop() }
+    fn is_empty(&self) -> bool { self.is_empty() }
+}
+
+#[rustfmt::skip]
+impl<T: Ord + Clone, H: MeldableHeap<T>> Heap<T> for Recording<H, T> {
+    fn new() -> Self { Self::new() }
+    fn push(&mut self, value: T) { self.push(value) }
+    fn pop(&mut self) -> Option<T> { self.pop() }
+    fn is_empty(&self) -> bool { self.is_empty() }
+}
+
 type Entry = [u64; 5];
 
 /*
 Benchmark results:
 
-push_pop_bench                          │               │               │               │         │
-├─ BinaryHeap<[u64; 5]>                 │               │               │               │         │
-│  ├─ 1000000             188.1 ms      │ 214.2 ms      │ 192.6 ms      │ 198.3 ms      │ 3       │ 3
-│  ├─ 2000000             527.2 ms      │ 557.2 ms      │ 555.4 ms      │ 546.6 ms      │ 3       │ 3
-│  ├─ 3000000             994.4 ms      │ 1.017 s       │ 995.7 ms      │ 1.002 s       │ 3       │ 3
-│  ├─ 4000000             1.445 s       │ 1.49 s        │ 1.487 s       │ 1.474 s       │ 3       │ 3
-│  ├─ 5000000             1.983 s       │ 2.016 s       │ 2.015 s       │ 2.005 s       │ 3       │ 3
-│  ├─ 6000000             2.5 s         │ 2.617 s       │ 2.53 s        │ 2.549 s       │ 3       │ 3
-│  ├─ 7000000             3.086 s       │ 3.151 s       │ 3.119 s       │ 3.119 s       │ 3       │ 3
-│  ╰─ 8000000             3.696 s       │ 3.805 s       │ 3.716 s       │ 3.739 s       │ 3       │ 3
-├─ LeftistHeap<[u64; 5]>                │               │               │               │         │
-│  ├─ 1000000             305.1 ms      │ 311.9 ms      │ 311.4 ms      │ 309.5 ms      │ 3       │ 3
-│  ├─ 2000000             768.4 ms      │ 789.8 ms      │ 784.8 ms      │ 781 ms        │ 3       │ 3
-│  ├─ 3000000             1.302 s       │ 1.34 s        │ 1.304 s       │ 1.316 s       │ 3       │ 3
-│  ├─ 4000000             1.894 s       │ 1.898 s       │ 1.898 s       │ 1.897 s       │ 3       │ 3
-│  ├─ 5000000             2.494 s       │ 2.539 s       │ 2.5 s         │ 2.511 s       │ 3       │ 3
-│  ├─ 6000000             3.149 s       │ 3.192 s       │ 3.184 s       │ 3.175 s       │ 3       │ 3
-│  ├─ 7000000             3.79 s        │ 3.945 s       │ 3.859 s       │ 3.865 s       │ 3       │ 3
-│  ╰─ 8000000             4.522 s       │ 4.562 s       │ 4.534 s       │ 4.539 s       │ 3       │ 3
-├─ PairingHeap<[u64; 5]>                 │               │               │               │         │
-│  ├─ 1000000             330.6 ms      │ 340.6 ms      │ 336.6 ms      │ 335.9 ms      │ 3       │ 3
-│  ├─ 2000000             926.7 ms      │ 947.2 ms      │ 944.6 ms      │ 939.5 ms      │ 3       │ 3
-│  ├─ 3000000             1.627 s       │ 1.658 s       │ 1.628 s       │ 1.638 s       │ 3       │ 3
-│  ├─ 4000000             2.395 s       │ 2.412 s       │ 2.408 s       │ 2.405 s       │ 3       │ 3
-│  ├─ 5000000             3.219 s       │ 3.282 s       │ 3.265 s       │ 3.255 s       │ 3       │ 3
-│  ├─ 6000000             4.107 s       │ 4.182 s       │ 4.15 s        │ 4.147 s       │ 3       │ 3
-│  ├─ 7000000             4.983 s       │ 5.13 s        │ 5.028 s       │ 5.047 s       │ 3       │ 3
-│  ╰─ 8000000             6.094 s       │ 6.218 s       │ 6.156 s       │ 6.156 s       │ 3       │ 3
-╰─ SkewHeap<[u64; 5]>                   │               │               │               │         │
-   ├─ 1000000             472.3 ms      │ 477.2 ms      │ 473.5 ms      │ 474.4 ms      │ 3       │ 3
-   ├─ 2000000             1.26 s        │ 1.311 s       │ 1.295 s       │ 1.288 s       │ 3       │ 3
-   ├─ 3000000             2.212 s       │ 2.232 s       │ 2.227 s       │ 2.224 s       │ 3       │ 3
-   ├─ 4000000             3.292 s       │ 3.338 s       │ 3.307 s       │ 3.312 s       │ 3       │ 3
-   ├─ 5000000             4.327 s       │ 4.454 s       │ 4.376 s       │ 4.386 s       │ 3       │ 3
-   ├─ 6000000             5.805 s       │ 6.279 s       │ 5.832 s       │ 5.972 s       │ 3       │ 3
-   ├─ 7000000             6.767 s       │ 7.14 s        │ 6.784 s       │ 6.897 s       │ 3       │ 3
-   ╰─ 8000000             7.94 s        │ 8.305 s       │ 7.993 s       │ 8.079 s       │ 3       │ 3
+push_pop_bench                                                                              │               │               │               │         │
+├─ BinaryHeap<[u64; 5]>                                                                     │               │               │               │         │
+│  ├─ 1000000                                                                 782.4 ms      │ 993.8 ms      │ 827.5 ms      │ 867.9 ms      │ 3       │ 3
+│  ├─ 2000000                                                                 1.937 s       │ 2.086 s       │ 2.027 s       │ 2.017 s       │ 3       │ 3
+│  ├─ 3000000                                                                 3.361 s       │ 4.374 s       │ 3.704 s       │ 3.813 s       │ 3       │ 3
+│  ├─ 4000000                                                                 4.851 s       │ 5.493 s       │ 5.074 s       │ 5.139 s       │ 3       │ 3
+│  ├─ 5000000                                                                 6.715 s       │ 7.387 s       │ 6.731 s       │ 6.944 s       │ 3       │ 3
+│  ├─ 6000000                                                                 8.112 s       │ 8.769 s       │ 8.444 s       │ 8.442 s       │ 3       │ 3
+│  ├─ 7000000                                                                 10.46 s       │ 12.91 s       │ 11.04 s       │ 11.47 s       │ 3       │ 3
+│  ╰─ 8000000                                                                 13.29 s       │ 15.85 s       │ 14.83 s       │ 14.66 s       │ 3       │ 3
+├─ BottomUpSkewHeap<[u64; 5]>                                                               │               │               │               │         │
+│  ├─ 1000000                                                                 1.405 s       │ 1.466 s       │ 1.455 s       │ 1.442 s       │ 3       │ 3
+│  ├─ 2000000                                                                 3.041 s       │ 3.203 s       │ 3.065 s       │ 3.103 s       │ 3       │ 3
+│  ├─ 3000000                                                                 4.955 s       │ 5.322 s       │ 5.117 s       │ 5.131 s       │ 3       │ 3
+│  ├─ 4000000                                                                 6.602 s       │ 7.667 s       │ 7.436 s       │ 7.235 s       │ 3       │ 3
+│  ├─ 5000000                                                                 9.617 s       │ 10 s          │ 9.635 s       │ 9.752 s       │ 3       │ 3
+│  ├─ 6000000                                                                 10.51 s       │ 12.34 s       │ 11.71 s       │ 11.52 s       │ 3       │ 3
+│  ├─ 7000000                                                                 10.69 s       │ 14.17 s       │ 13.09 s       │ 12.65 s       │ 3       │ 3
+│  ╰─ 8000000                                                                 13.2 s        │ 16.85 s       │ 14.23 s       │ 14.76 s       │ 3       │ 3
+├─ DoubleEndedPairingHeap<[u64; 5]>                                                         │               │               │               │         │
+│  ├─ 1000000                                                                 2.353 s       │ 2.457 s       │ 2.403 s       │ 2.404 s       │ 3       │ 3
+│  ├─ 2000000                                                                 5.517 s       │ 5.79 s        │ 5.732 s       │ 5.68 s        │ 3       │ 3
+│  ├─ 3000000                                                                 8.779 s       │ 9.472 s       │ 8.885 s       │ 9.045 s       │ 3       │ 3
+│  ├─ 4000000                                                                 11.19 s       │ 12.71 s       │ 12.49 s       │ 12.13 s       │ 3       │ 3
+│  ├─ 5000000                                                                 14.3 s        │ 15.98 s       │ 15.26 s       │ 15.18 s       │ 3       │ 3
+│  ├─ 6000000                                                                 18.39 s       │ 21.12 s       │ 19.66 s       │ 19.73 s       │ 3       │ 3
+│  ├─ 7000000                                                                 20.34 s       │ 23.17 s       │ 22.3 s        │ 21.94 s       │ 3       │ 3
+│  ╰─ 8000000                                                                 24.65 s       │ 25.78 s       │ 25.66 s       │ 25.36 s       │ 3       │ 3
+├─ FibonacciHeap<[u64; 5]>                                                                  │               │               │               │         │
+│  ├─ 1000000                                                                 2.468 s       │ 2.605 s       │ 2.582 s       │ 2.552 s       │ 3       │ 3
+│  ├─ 2000000                                                                 6.456 s       │ 6.618 s       │ 6.475 s       │ 6.516 s       │ 3       │ 3
+│  ├─ 3000000                                                                 10.07 s       │ 11.95 s       │ 10.1 s        │ 10.71 s       │ 3       │ 3
+│  ├─ 4000000                                                                 12.67 s       │ 15.29 s       │ 14.3 s        │ 14.09 s       │ 3       │ 3
+│  ├─ 5000000                                                                 13.94 s       │ 16.07 s       │ 15.93 s       │ 15.31 s       │ 3       │ 3
+│  ├─ 6000000                                                                 17.69 s       │ 21.22 s       │ 20.46 s       │ 19.79 s       │ 3       │ 3
+│  ├─ 7000000                                                                 23.44 s       │ 28.39 s       │ 24.15 s       │ 25.33 s       │ 3       │ 3
+│  ╰─ 8000000                                                                 25.43 s       │ 31.97 s       │ 30.1 s        │ 29.17 s       │ 3       │ 3
+├─ HollowHeap<[u64; 5]>                                                                     │               │               │               │         │
+│  ├─ 1000000                                                                 2.22 s        │ 2.464 s       │ 2.23 s        │ 2.305 s       │ 3       │ 3
+│  ├─ 2000000                                                                 5.29 s        │ 6.718 s       │ 5.467 s       │ 5.825 s       │ 3       │ 3
+│  ├─ 3000000                                                                 7.744 s       │ 10.04 s       │ 8.922 s       │ 8.903 s       │ 3       │ 3
+│  ├─ 4000000                                                                 10.2 s        │ 14.71 s       │ 11.99 s       │ 12.3 s        │ 3       │ 3
+│  ├─ 5000000                                                                 18.9 s        │ 20.14 s       │ 19.08 s       │ 19.38 s       │ 3       │ 3
+│  ├─ 6000000                                                                 22.53 s       │ 25.31 s       │ 23.83 s       │ 23.89 s       │ 3       │ 3
+│  ├─ 7000000                                                                 25.29 s       │ 27.35 s       │ 25.53 s       │ 26.06 s       │ 3       │ 3
+│  ╰─ 8000000                                                                 30.32 s       │ 33.15 s       │ 30.43 s       │ 31.3 s        │ 3       │ 3
+├─ LeftistHeap<[u64; 5]>                                                                    │               │               │               │         │
+│  ├─ 1000000                                                                 876.4 ms      │ 1.025 s       │ 927 ms        │ 942.8 ms      │ 3       │ 3
+│  ├─ 2000000                                                                 2.292 s       │ 2.61 s        │ 2.361 s       │ 2.421 s       │ 3       │ 3
+│  ├─ 3000000                                                                 3.808 s       │ 4.325 s       │ 4.126 s       │ 4.087 s       │ 3       │ 3
+│  ├─ 4000000                                                                 5.543 s       │ 6.667 s       │ 6.145 s       │ 6.118 s       │ 3       │ 3
+│  ├─ 5000000                                                                 8.253 s       │ 8.746 s       │ 8.531 s       │ 8.51 s        │ 3       │ 3
+│  ├─ 6000000                                                                 10.42 s       │ 10.99 s       │ 10.95 s       │ 10.79 s       │ 3       │ 3
+│  ├─ 7000000                                                                 11.72 s       │ 13.27 s       │ 12.96 s       │ 12.65 s       │ 3       │ 3
+│  ╰─ 8000000                                                                 13.27 s       │ 15.42 s       │ 13.9 s        │ 14.2 s        │ 3       │ 3
+├─ PairingHeap<[u64; 5], meldable_heaps::pairing_strategy::AuxiliaryTwoPass>                │               │               │               │         │
+│  ├─ 1000000                                                                 1.302 s       │ 1.566 s       │ 1.334 s       │ 1.401 s       │ 3       │ 3
+│  ├─ 2000000                                                                 3.195 s       │ 3.411 s       │ 3.246 s       │ 3.284 s       │ 3       │ 3
+│  ├─ 3000000                                                                 5.793 s       │ 6.763 s       │ 6.723 s       │ 6.426 s       │ 3       │ 3
+│  ├─ 4000000                                                                 9.185 s       │ 9.475 s       │ 9.335 s       │ 9.332 s       │ 3       │ 3
+│  ├─ 5000000                                                                 10.39 s       │ 11.59 s       │ 11.01 s       │ 11 s          │ 3       │ 3
+│  ├─ 6000000                                                                 14.28 s       │ 18.33 s       │ 15.76 s       │ 16.12 s       │ 3       │ 3
+│  ├─ 7000000                                                                 20.5 s        │ 22.57 s       │ 21.99 s       │ 21.69 s       │ 3       │ 3
+│  ╰─ 8000000                                                                 20.24 s       │ 25.36 s       │ 22.86 s       │ 22.82 s       │ 3       │ 3
+├─ PairingHeap<[u64; 5], meldable_heaps::pairing_strategy::Multipass>                       │               │               │               │         │
+│  ├─ 1000000                                                                 1.454 s       │ 2.085 s       │ 1.456 s       │ 1.665 s       │ 3       │ 3
+│  ├─ 2000000                                                                 3.32 s        │ 4.147 s       │ 3.876 s       │ 3.781 s       │ 3       │ 3
+│  ├─ 3000000                                                                 7.301 s       │ 8.785 s       │ 8.019 s       │ 8.035 s       │ 3       │ 3
+│  ├─ 4000000                                                                 9.683 s       │ 11.59 s       │ 10.14 s       │ 10.47 s       │ 3       │ 3
+│  ├─ 5000000                                                                 12.51 s       │ 14.18 s       │ 13.38 s       │ 13.36 s       │ 3       │ 3
+│  ├─ 6000000                                                                 19.96 s       │ 22.64 s       │ 21.29 s       │ 21.3 s        │ 3       │ 3
+│  ├─ 7000000                                                                 22.97 s       │ 28.21 s       │ 27.56 s       │ 26.25 s       │ 3       │ 3
+│  ╰─ 8000000                                                                 28.04 s       │ 29.92 s       │ 28.98 s       │ 28.98 s       │ 3       │ 3
+├─ PairingHeap<[u64; 5]>                                                                    │               │               │               │         │
+│  ├─ 1000000                                                                 1.55 s        │ 1.626 s       │ 1.572 s       │ 1.583 s       │ 3       │ 3
+│  ├─ 2000000                                                                 3.908 s       │ 4.27 s        │ 3.947 s       │ 4.042 s       │ 3       │ 3
+│  ├─ 3000000                                                                 5.761 s       │ 6.056 s       │ 5.931 s       │ 5.916 s       │ 3       │ 3
+│  ├─ 4000000                                                                 9.784 s       │ 9.909 s       │ 9.821 s       │ 9.838 s       │ 3       │ 3
+│  ├─ 5000000                                                                 12.21 s       │ 13.12 s       │ 13.03 s       │ 12.79 s       │ 3       │ 3
+│  ├─ 6000000                                                                 15.23 s       │ 15.65 s       │ 15.58 s       │ 15.49 s       │ 3       │ 3
+│  ├─ 7000000                                                                 19.4 s        │ 20.96 s       │ 19.75 s       │ 20.04 s       │ 3       │ 3
+│  ╰─ 8000000                                                                 26.54 s       │ 28.47 s       │ 27.13 s       │ 27.38 s       │ 3       │ 3
+├─ RandomizedMeldableHeap<[u64; 5]>                                                         │               │               │               │         │
+│  ├─ 1000000                                                                 1.366 s       │ 1.48 s        │ 1.445 s       │ 1.43 s        │ 3       │ 3
+│  ├─ 2000000                                                                 3.392 s       │ 3.85 s        │ 3.776 s       │ 3.673 s       │ 3       │ 3
+│  ├─ 3000000                                                                 5.013 s       │ 5.567 s       │ 5.177 s       │ 5.252 s       │ 3       │ 3
+│  ├─ 4000000                                                                 8.052 s       │ 9.282 s       │ 8.241 s       │ 8.525 s       │ 3       │ 3
+│  ├─ 5000000                                                                 9.813 s       │ 10.81 s       │ 10.39 s       │ 10.34 s       │ 3       │ 3
+│  ├─ 6000000                                                                 16.34 s       │ 17.01 s       │ 16.6 s        │ 16.65 s       │ 3       │ 3
+│  ├─ 7000000                                                                 15.07 s       │ 21.22 s       │ 15.37 s       │ 17.22 s       │ 3       │ 3
+│  ╰─ 8000000                                                                 19.63 s       │ 21.51 s       │ 21.01 s       │ 20.72 s       │ 3       │ 3
+├─ RankPairingHeap<[u64; 5], meldable_heaps::rank_pairing_heap::Type1Rank>                  │               │               │               │         │
+│  ├─ 1000000                                                                 2.891 s       │ 3.257 s       │ 2.912 s       │ 3.02 s        │ 3       │ 3
+│  ├─ 2000000                                                                 6.874 s       │ 7.818 s       │ 7.252 s       │ 7.314 s       │ 3       │ 3
+│  ├─ 3000000                                                                 11.07 s       │ 11.92 s       │ 11.8 s        │ 11.6 s        │ 3       │ 3
+│  ├─ 4000000                                                                 14.39 s       │ 15.37 s       │ 15.36 s       │ 15.04 s       │ 3       │ 3
+│  ├─ 5000000                                                                 19.46 s       │ 21.89 s       │ 21.38 s       │ 20.91 s       │ 3       │ 3
+│  ├─ 6000000                                                                 25.11 s       │ 25.89 s       │ 25.35 s       │ 25.45 s       │ 3       │ 3
+│  ├─ 7000000                                                                 29.28 s       │ 39.04 s       │ 36.16 s       │ 34.82 s       │ 3       │ 3
+│  ╰─ 8000000                                                                 40.39 s       │ 46.87 s       │ 44.1 s        │ 43.79 s       │ 3       │ 3
+├─ RankPairingHeap<[u64; 5]>                                                                │               │               │               │         │
+│  ├─ 1000000                                                                 3.517 s       │ 4.009 s       │ 3.903 s       │ 3.809 s       │ 3       │ 3
+│  ├─ 2000000                                                                 8.014 s       │ 8.341 s       │ 8.144 s       │ 8.166 s       │ 3       │ 3
+│  ├─ 3000000                                                                 10.82 s       │ 11.24 s       │ 10.89 s       │ 10.98 s       │ 3       │ 3
+│  ├─ 4000000                                                                 14.61 s       │ 19.08 s       │ 16.96 s       │ 16.88 s       │ 3       │ 3
+│  ├─ 5000000                                                                 23.2 s        │ 24.43 s       │ 23.97 s       │ 23.87 s       │ 3       │ 3
+│  ├─ 6000000                                                                 30.08 s       │ 34.19 s       │ 30.9 s        │ 31.72 s       │ 3       │ 3
+│  ├─ 7000000                                                                 33.09 s       │ 37.02 s       │ 35.6 s        │ 35.23 s       │ 3       │ 3
+│  ╰─ 8000000                                                                 36.67 s       │ 43.38 s       │ 40.36 s       │ 40.14 s       │ 3       │ 3
+├─ SkewBinomialHeap<[u64; 5]>                                                               │               │               │               │         │
+│  ├─ 1000000                                                                 2.421 s       │ 2.638 s       │ 2.475 s       │ 2.511 s       │ 3       │ 3
+│  ├─ 2000000                                                                 6.165 s       │ 8.293 s       │ 6.174 s       │ 6.877 s       │ 3       │ 3
+│  ├─ 3000000                                                                 13.13 s       │ 14.08 s       │ 13.54 s       │ 13.58 s       │ 3       │ 3
+│  ├─ 4000000                                                                 16.33 s       │ 19.53 s       │ 16.82 s       │ 17.56 s       │ 3       │ 3
+│  ├─ 5000000                                                                 23.27 s       │ 25.98 s       │ 24.04 s       │ 24.43 s       │ 3       │ 3
+│  ├─ 6000000                                                                 26.66 s       │ 31.28 s       │ 27.42 s       │ 28.45 s       │ 3       │ 3
+│  ├─ 7000000                                                                 32.6 s        │ 38.98 s       │ 37.19 s       │ 36.26 s       │ 3       │ 3
+│  ╰─ 8000000                                                                 42.11 s       │ 46.09 s       │ 44.99 s       │ 44.4 s        │ 3       │ 3
+├─ SkewHeap<[u64; 5]>                                                                       │               │               │               │         │
+│  ├─ 1000000                                                                 1.275 s       │ 1.349 s       │ 1.295 s       │ 1.307 s       │ 3       │ 3
+│  ├─ 2000000                                                                 3.2 s         │ 3.466 s       │ 3.205 s       │ 3.29 s        │ 3       │ 3
+│  ├─ 3000000                                                                 5.235 s       │ 5.397 s       │ 5.29 s        │ 5.308 s       │ 3       │ 3
+│  ├─ 4000000                                                                 7.613 s       │ 8.242 s       │ 8.077 s       │ 7.978 s       │ 3       │ 3
+│  ├─ 5000000                                                                 10.5 s        │ 11.6 s        │ 11.47 s       │ 11.19 s       │ 3       │ 3
+│  ├─ 6000000                                                                 13.09 s       │ 14.38 s       │ 14.29 s       │ 13.92 s       │ 3       │ 3
+│  ├─ 7000000                                                                 14.66 s       │ 16.43 s       │ 15.44 s       │ 15.51 s       │ 3       │ 3
+│  ╰─ 8000000                                                                 18.72 s       │ 19.34 s       │ 18.98 s       │ 19.01 s       │ 3       │ 3
+├─ SmoothHeap<[u64; 5]>                                                                     │               │               │               │         │
+│  ├─ 1000000                                                                 1.253 s       │ 1.468 s       │ 1.262 s       │ 1.328 s       │ 3       │ 3
+│  ├─ 2000000                                                                 2.856 s       │ 2.9 s         │ 2.858 s       │ 2.872 s       │ 3       │ 3
+│  ├─ 3000000                                                                 4.877 s       │ 4.996 s       │ 4.906 s       │ 4.926 s       │ 3       │ 3
+│  ├─ 4000000                                                                 6.308 s       │ 6.663 s       │ 6.648 s       │ 6.54 s        │ 3       │ 3
+│  ├─ 5000000                                                                 8.606 s       │ 8.742 s       │ 8.624 s       │ 8.658 s       │ 3       │ 3
+│  ├─ 6000000                                                                 10.17 s       │ 10.68 s       │ 10.56 s       │ 10.47 s       │ 3       │ 3
+│  ├─ 7000000                                                                 13.47 s       │ 16.14 s       │ 15.89 s       │ 15.17 s       │ 3       │ 3
+│  ╰─ 8000000                                                                 16.77 s       │ 18.28 s       │ 18.11 s       │ 17.72 s       │ 3       │ 3
+╰─ WeightBiasedLeftistHeap<[u64; 5]>                                                        │               │               │               │         │
+   ├─ 1000000                                                                 1.206 s       │ 1.259 s       │ 1.244 s       │ 1.236 s       │ 3       │ 3
+   ├─ 2000000                                                                 2.551 s       │ 2.649 s       │ 2.579 s       │ 2.593 s       │ 3       │ 3
+   ├─ 3000000                                                                 4.067 s       │ 4.406 s       │ 4.309 s       │ 4.26 s        │ 3       │ 3
+   ├─ 4000000                                                                 5.811 s       │ 6.01 s        │ 5.967 s       │ 5.93 s        │ 3       │ 3
+   ├─ 5000000                                                                 7.446 s       │ 7.776 s       │ 7.612 s       │ 7.611 s       │ 3       │ 3
+   ├─ 6000000                                                                 9.976 s       │ 10.42 s       │ 10.12 s       │ 10.17 s       │ 3       │ 3
+   ├─ 7000000                                                                 11.72 s       │ 12.51 s       │ 12.1 s        │ 12.11 s       │ 3       │ 3
+   ╰─ 8000000                                                                 14.88 s       │ 15.64 s       │ 15.36 s       │ 15.29 s       │ 3       │ 3
 */
 #[divan::bench(
-    types = [BinaryHeap<Entry>, PairingHeap<Entry>, SkewHeap<Entry>, LeftistHeap<Entry>],
+    types = [
+        BinaryHeap<Entry>, PairingHeap<Entry>, SkewHeap<Entry>, LeftistHeap<Entry>,
+        RankPairingHeap<Entry>, RankPairingHeap<Entry, Type1Rank>, HollowHeap<Entry>, FibonacciHeap<Entry>,
+        SkewBinomialHeap<Entry>, WeightBiasedLeftistHeap<Entry>,
+        RandomizedMeldableHeap<Entry>, BottomUpSkewHeap<Entry>, SmoothHeap<Entry>,
+        PairingHeap<Entry, Multipass>, PairingHeap<Entry, AuxiliaryTwoPass>,
+        DoubleEndedPairingHeap<Entry>,
+    ],
     args = [1000000, 2000000, 3000000, 4000000, 5000000, 6000000, 7000000, 8000000],
     sample_count = 3,
 )]
@@ -114,49 +331,15 @@ fn push_pop<H: Heap<Entry>>(n: u64, heap: &mut H) {
     }
 }
 
-/*
-Benchmark results:
-
-random_push_pop_bench                   │               │               │               │         │
-├─ BinaryHeap<[u64; 5]>                 │               │               │               │         │
-│  ├─ 1000000             32.63 ms      │ 46.66 ms      │ 36.42 ms      │ 37.66 ms      │ 5       │ 5
-│  ├─ 2000000             67.71 ms      │ 86.06 ms      │ 72.56 ms      │ 74.41 ms      │ 5       │ 5
-│  ├─ 3000000             108 ms        │ 145.8 ms      │ 113.7 ms      │ 119.7 ms      │ 5       │ 5
-│  ├─ 4000000             146.7 ms      │ 176.1 ms      │ 152.6 ms      │ 156.5 ms      │ 5       │ 5
-│  ├─ 5000000             188.7 ms      │ 218.9 ms      │ 201.9 ms      │ 200.9 ms      │ 5       │ 5
-│  ├─ 6000000             220.3 ms      │ 305.3 ms      │ 233.5 ms      │ 246.8 ms      │ 5       │ 5
-│  ├─ 7000000             257.4 ms      │ 334.9 ms      │ 274 ms        │ 284.1 ms      │ 5       │ 5
-│  ╰─ 8000000             307.5 ms      │ 400 ms        │ 318.2 ms      │ 331.2 ms      │ 5       │ 5
-├─ LeftistHeap<[u64; 5]>                │               │               │               │         │
-│  ├─ 1000000             30.59 ms      │ 31.52 ms      │ 31.26 ms      │ 31.2 ms       │ 5       │ 5
-│  ├─ 2000000             61.23 ms      │ 63.37 ms      │ 62.73 ms      │ 62.52 ms      │ 5       │ 5
-│  ├─ 3000000             91.64 ms      │ 96.05 ms      │ 93.44 ms      │ 93.53 ms      │ 5       │ 5
-│  ├─ 4000000             125.8 ms      │ 139.7 ms      │ 131.5 ms      │ 131.8 ms      │ 5       │ 5
-│  ├─ 5000000             153.1 ms      │ 169.7 ms      │ 166.7 ms      │ 162.9 ms      │ 5       │ 5
-│  ├─ 6000000             184.3 ms      │ 211.2 ms      │ 195.9 ms      │ 198.9 ms      │ 5       │ 5
-│  ├─ 7000000             227.4 ms      │ 251 ms        │ 242.7 ms      │ 239.4 ms      │ 5       │ 5
-│  ╰─ 8000000             256.4 ms      │ 289.9 ms      │ 273.2 ms      │ 273.5 ms      │ 5       │ 5
-├─ PairingHeap<[u64; 5]>                 │               │               │               │         │
-│  ├─ 1000000             23.65 ms      │ 28.91 ms      │ 28.39 ms      │ 27.51 ms      │ 5       │ 5
-│  ├─ 2000000             47.12 ms      │ 56.89 ms      │ 52.25 ms      │ 52.54 ms      │ 5       │ 5
-│  ├─ 3000000             74.35 ms      │ 88.75 ms      │ 81.01 ms      │ 81.35 ms      │ 5       │ 5
-│  ├─ 4000000             95.74 ms      │ 115.4 ms      │ 102.8 ms      │ 104.9 ms      │ 5       │ 5
-│  ├─ 5000000             117.4 ms      │ 145.6 ms      │ 135.4 ms      │ 133.9 ms      │ 5       │ 5
-│  ├─ 6000000             145.6 ms      │ 172.4 ms      │ 147.6 ms      │ 152.1 ms      │ 5       │ 5
-│  ├─ 7000000             175.2 ms      │ 241 ms        │ 195.3 ms      │ 201.6 ms      │ 5       │ 5
-│  ╰─ 8000000             190.5 ms      │ 229.2 ms      │ 207.2 ms      │ 209.4 ms      │ 5       │ 5
-╰─ SkewHeap<[u64; 5]>                   │               │               │               │         │
-   ├─ 1000000             33.67 ms      │ 38.21 ms      │ 37.67 ms      │ 36.98 ms      │ 5       │ 5
-   ├─ 2000000             64.52 ms      │ 76.14 ms      │ 70.64 ms      │ 71.08 ms      │ 5       │ 5
-   ├─ 3000000             101 ms        │ 116.1 ms      │ 104.4 ms      │ 107.1 ms      │ 5       │ 5
-   ├─ 4000000             130.9 ms      │ 164.9 ms      │ 136.3 ms      │ 142.1 ms      │ 5       │ 5
-   ├─ 5000000             168.9 ms      │ 203.2 ms      │ 169.6 ms      │ 176.7 ms      │ 5       │ 5
-   ├─ 6000000             200 ms        │ 228.8 ms      │ 206.5 ms      │ 210.9 ms      │ 5       │ 5
-   ├─ 7000000             233.9 ms      │ 259.5 ms      │ 239 ms        │ 244.7 ms      │ 5       │ 5
-   ╰─ 8000000             266.6 ms      │ 307 ms        │ 291.1 ms      │ 288 ms        │ 5       │ 5
-*/
 #[divan::bench(
-    types = [BinaryHeap<Entry>, PairingHeap<Entry>, SkewHeap<Entry>, LeftistHeap<Entry>],
+    types = [
+        BinaryHeap<Entry>, PairingHeap<Entry>, SkewHeap<Entry>, LeftistHeap<Entry>,
+        RankPairingHeap<Entry>, RankPairingHeap<Entry, Type1Rank>, HollowHeap<Entry>, FibonacciHeap<Entry>,
+        SkewBinomialHeap<Entry>, WeightBiasedLeftistHeap<Entry>,
+        RandomizedMeldableHeap<Entry>, BottomUpSkewHeap<Entry>, SmoothHeap<Entry>,
+        PairingHeap<Entry, Multipass>, PairingHeap<Entry, AuxiliaryTwoPass>,
+        DoubleEndedPairingHeap<Entry>,
+    ],
     args = [1000000, 2000000, 3000000, 4000000, 5000000, 6000000, 7000000, 8000000],
     sample_count = 5,
 )]
@@ -175,3 +358,135 @@ fn random_push_pop<H: Heap<Entry>>(n: u64, heap: &mut H) {
         }
     }
 }
+
+// `FrontToBack` and `BackToFront` do not guarantee O(log n) amortized `pop`,
+// so all strategies are compared with smaller inputs.
+#[divan::bench(
+    types = [
+        PairingHeap<Entry, TwoPass>, PairingHeap<Entry, Multipass>, PairingHeap<Entry, FrontToBack>,
+        PairingHeap<Entry, BackToFront>, PairingHeap<Entry, AuxiliaryTwoPass>,
+    ],
+    args = [1000, 10000],
+    sample_count = 5,
+)]
+fn pairing_strategy_bench<H: Heap<Entry>>(bencher: Bencher, n: u64) {
+    let mut heap = H::new();
+    bencher.bench_local(|| push_pop(n, &mut heap));
+}
+
+/*
+Benchmark results:
+
+monotone_push_pop_bench                       │               │               │               │         │
+├─ CalendarQueue<(u64, u64)>                  │               │               │               │         │
+│  ├─ 1000000                   122.8 ms      │ 148.4 ms      │ 140.4 ms      │ 138.3 ms      │ 5       │ 5
+│  ├─ 2000000                   345.1 ms      │ 366.2 ms      │ 357.1 ms      │ 358 ms        │ 5       │ 5
+│  ├─ 4000000                   769.5 ms      │ 1.061 s       │ 875.2 ms      │ 897.9 ms      │ 5       │ 5
+│  ╰─ 8000000                   2.107 s       │ 2.486 s       │ 2.295 s       │ 2.3 s         │ 5       │ 5
+├─ PairingHeap<(u64, u64)>                    │               │               │               │         │
+│  ├─ 1000000                   505.1 ms      │ 582.7 ms      │ 560 ms        │ 554.3 ms      │ 5       │ 5
+│  ├─ 2000000                   1.524 s       │ 1.735 s       │ 1.619 s       │ 1.627 s       │ 5       │ 5
+│  ├─ 4000000                   4.238 s       │ 5.229 s       │ 5.006 s       │ 4.796 s       │ 5       │ 5
+│  ╰─ 8000000                   12.45 s       │ 14.65 s       │ 13.82 s       │ 13.52 s       │ 5       │ 5
+├─ RadixHeap<u64, u64>                        │               │               │               │         │
+│  ├─ 1000000                   35.66 ms      │ 37.02 ms      │ 36.52 ms      │ 36.33 ms      │ 5       │ 5
+│  ├─ 2000000                   77.7 ms       │ 126.7 ms      │ 84.88 ms      │ 97.23 ms      │ 5       │ 5
+│  ├─ 4000000                   172.4 ms      │ 241.1 ms      │ 224.7 ms      │ 213.8 ms      │ 5       │ 5
+│  ╰─ 8000000                   404.2 ms      │ 549.7 ms      │ 452.6 ms      │ 471 ms        │ 5       │ 5
+╰─ RankPairingHeap<(u64, u64)>                │               │               │               │         │
+   ├─ 1000000                   1.208 s       │ 1.497 s       │ 1.332 s       │ 1.369 s       │ 5       │ 5
+   ├─ 2000000                   3.211 s       │ 4.396 s       │ 4.19 s        │ 3.926 s       │ 5       │ 5
+   ├─ 4000000                   10.55 s       │ 11.87 s       │ 10.98 s       │ 11.02 s       │ 5       │ 5
+   ╰─ 8000000                   25.09 s       │ 26.71 s       │ 26.22 s       │ 26.11 s       │ 5       │ 5
+*/
+// Pops are monotone like Dijkstra's algorithm: each popped key is followed by a push of a greater key.
+#[divan::bench(
+    types = [
+        PairingHeap<(u64, u64)>, RankPairingHeap<(u64, u64)>, RadixHeap<u64, u64>,
+        CalendarQueue<(u64, u64)>,
+    ],
+    args = [1000000, 2000000, 4000000, 8000000],
+    sample_count = 5,
+)]
+fn monotone_push_pop_bench<H: Heap<(u64, u64)>>(bencher: Bencher, n: u64) {
+    bencher.bench_local(|| monotone_push_pop(n, &mut H::new()));
+}
+
+fn monotone_push_pop<H: Heap<(u64, u64)>>(n: u64, heap: &mut H) {
+    let mut rng = SmallRng::seed_from_u64(2635249153387078803);
+    for i in 0..n / 10 {
+        heap.push((rng.gen_range(0..1000), i));
+    }
+    for i in 0..n {
+        let (key, _) = heap.pop().unwrap();
+        heap.push((key + rng.gen_range(1..1000), i));
+    }
+    while !heap.is_empty() {
+        divan::black_box(heap.pop());
+    }
+}
+
+// The synthetic workloads are recorded once into traces, which are replayed against each heap,
+// so that all heaps receive exactly the same sequence of operations.
+fn record(workload: &str, n: u64) -> Trace<Entry> {
+    let mut heap = Recording::<PairingHeap<Entry>, Entry>::new();
+    match workload {
+        "push_pop" => push_pop(n, &mut heap),
+        "random_push_pop" => random_push_pop(n, &mut heap),
+        _ => unreachable!(),
+    }
+    // Round-trip through the binary format, as a trace loaded from a file would be.
+    Trace::decode(&heap.into_trace().encode()).unwrap()
+}
+
+/*
+Benchmark results:
+
+replay_bench                                        │               │               │               │         │
+├─ BottomUpSkewHeap<[u64; 5]>                       │               │               │               │         │
+│  ├─ push_pop                        1.14 s        │ 1.401 s       │ 1.288 s       │ 1.268 s       │ 5       │ 5
+│  ╰─ random_push_pop                 81.79 ms      │ 101.4 ms      │ 86.8 ms       │ 91.19 ms      │ 5       │ 5
+├─ FibonacciHeap<[u64; 5]>                          │               │               │               │         │
+│  ├─ push_pop                        3.381 s       │ 3.905 s       │ 3.458 s       │ 3.55 s        │ 5       │ 5
+│  ╰─ random_push_pop                 266.6 ms      │ 320.9 ms      │ 275.4 ms      │ 282.7 ms      │ 5       │ 5
+├─ HollowHeap<[u64; 5]>                             │               │               │               │         │
+│  ├─ push_pop                        2.508 s       │ 3.251 s       │ 2.69 s        │ 2.754 s       │ 5       │ 5
+│  ╰─ random_push_pop                 200.3 ms      │ 238.4 ms      │ 210.9 ms      │ 212.5 ms      │ 5       │ 5
+├─ LeftistHeap<[u64; 5]>                            │               │               │               │         │
+│  ├─ push_pop                        1.212 s       │ 1.321 s       │ 1.268 s       │ 1.265 s       │ 5       │ 5
+│  ╰─ random_push_pop                 116.9 ms      │ 137.1 ms      │ 119.4 ms      │ 124 ms        │ 5       │ 5
+├─ PairingHeap<[u64; 5]>                            │               │               │               │         │
+│  ├─ push_pop                        1.61 s        │ 2.056 s       │ 1.895 s       │ 1.869 s       │ 5       │ 5
+│  ╰─ random_push_pop                 98.15 ms      │ 120.6 ms      │ 98.61 ms      │ 104.2 ms      │ 5       │ 5
+├─ RandomizedMeldableHeap<[u64; 5]>                 │               │               │               │         │
+│  ├─ push_pop                        1.34 s        │ 1.634 s       │ 1.406 s       │ 1.445 s       │ 5       │ 5
+│  ╰─ random_push_pop                 125 ms        │ 155.4 ms      │ 130.1 ms      │ 134.5 ms      │ 5       │ 5
+├─ RankPairingHeap<[u64; 5]>                        │               │               │               │         │
+│  ├─ push_pop                        3.261 s       │ 3.707 s       │ 3.442 s       │ 3.504 s       │ 5       │ 5
+│  ╰─ random_push_pop                 277.5 ms      │ 328.5 ms      │ 296 ms        │ 301.1 ms      │ 5       │ 5
+├─ SkewBinomialHeap<[u64; 5]>                       │               │               │               │         │
+│  ├─ push_pop                        3.482 s       │ 3.66 s        │ 3.598 s       │ 3.577 s       │ 5       │ 5
+│  ╰─ random_push_pop                 288.9 ms      │ 326.8 ms      │ 299.8 ms      │ 303.4 ms      │ 5       │ 5
+├─ SkewHeap<[u64; 5]>                               │               │               │               │         │
+│  ├─ push_pop                        1.283 s       │ 1.438 s       │ 1.416 s       │ 1.39 s        │ 5       │ 5
+│  ╰─ random_push_pop                 132.7 ms      │ 168.4 ms      │ 139.4 ms      │ 142.8 ms      │ 5       │ 5
+├─ SmoothHeap<[u64; 5]>                             │               │               │               │         │
+│  ├─ push_pop                        1.247 s       │ 1.381 s       │ 1.322 s       │ 1.309 s       │ 5       │ 5
+│  ╰─ random_push_pop                 99.92 ms      │ 115.2 ms      │ 101 ms        │ 104.2 ms      │ 5       │ 5
+╰─ WeightBiasedLeftistHeap<[u64; 5]>                │               │               │               │         │
+   ├─ push_pop                        1.09 s        │ 1.271 s       │ 1.132 s       │ 1.148 s       │ 5       │ 5
+   ╰─ random_push_pop                 118.3 ms      │ 147.1 ms      │ 133.9 ms      │ 133.9 ms      │ 5       │ 5
+*/
+#[divan::bench(
+    types = [
+        PairingHeap<Entry>, SkewHeap<Entry>, LeftistHeap<Entry>, RankPairingHeap<Entry>,
+        HollowHeap<Entry>, FibonacciHeap<Entry>, SkewBinomialHeap<Entry>, WeightBiasedLeftistHeap<Entry>,
+        RandomizedMeldableHeap<Entry>, BottomUpSkewHeap<Entry>, SmoothHeap<Entry>,
+    ],
+    args = ["push_pop", "random_push_pop"],
+    sample_count = 5,
+)]
+fn replay_bench<H: MeldableHeap<Entry>>(bencher: Bencher, workload: &str) {
+    let trace = record(workload, 1000000);
+    bencher.bench_local(|| trace.replay::<H>(NoClock));
+}
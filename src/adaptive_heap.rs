@@ -0,0 +1,645 @@
+#![forbid(unsafe_code)]
+
+use core::cmp::Ordering;
+
+use alloc::vec::Vec;
+
+use crate::{AddressableHeap, AnyHeap, Handle, HeapKind, MeldableHeap};
+
+/// `AdaptiveHeap` is a priority queue which migrates between [`PairingHeap`](crate::PairingHeap),
+/// [`LeftistHeap`](crate::LeftistHeap) and [`SkewHeap`](crate::SkewHeap) according to the observed workload.
+/// `AdaptiveHeap` is a **min-heap**, which means that the minimum element is popped first.
+///
+/// The heap counts its operations and measures the latency of `pop` with the clock `C` in windows.
+/// At the end of each window, the backend is chosen by [`AdaptivePolicy`]:
+/// - `LeftistHeap` if too many pops are slower than the latency limit, because its `pop` is O(log n) in the worst case,
+/// - `PairingHeap` if pushes and melds are the majority, because its `push` and `meld` are O(1),
+/// - `LeftistHeap` if pops are the majority,
+/// - `SkewHeap` otherwise.
+///
+/// A migration moves all elements in O(n) time, but a window lasts at least n operations,
+/// so migrations add only O(1) amortized time to each operation.
+/// A migration happens inside an operation and does not change the set of elements,
+/// so callers do not observe it except for [`AdaptiveHeap::kind`].
+///
+/// # Handles
+/// The backends are not addressable, so [`push_with_handle`](Self::push_with_handle) returns a [`Handle`]
+/// to a slot of a table kept beside the backend, which holds a copy of the element.
+/// A migration moves the entries of the backend but not the table, so handles stay valid across migrations.
+/// `decrease_key` pushes a new entry and `remove` empties the slot, and the old entries are discarded
+/// when they reach the top of the backend or at the next migration.
+/// This is why the handle operations require `T: Clone`.
+pub struct AdaptiveHeap<T: Ord, C: Clock = NoClock> {
+    heap: AnyHeap<Entry<T>>,
+    // The table of handles, indexed by `Handle`
+    slots: Vec<Slot<T>>,
+    free: Vec<usize>,
+    // The number of entries in the backend which are replaced by `decrease_key` or removed by `remove`
+    stale: usize,
+    len: usize,
+    policy: AdaptivePolicy,
+    clock: C,
+    // The operations in the current window
+    workload: Workload,
+    migrations: u64,
+}
+
+impl<T: Ord> AdaptiveHeap<T> {
+    /// Constructs a empty `AdaptiveHeap` with the default policy and without latency measurement.
+    /// O(1) time.
+    pub fn new() -> Self {
+        Self::with_clock(AdaptivePolicy::default(), NoClock)
+    }
+
+    /// Constructs a empty `AdaptiveHeap` with the given policy and without latency measurement.
+    /// O(1) time.
+    pub fn with_policy(policy: AdaptivePolicy) -> Self {
+        Self::with_clock(policy, NoClock)
+    }
+}
+
+impl<T: Ord, C: Clock> AdaptiveHeap<T, C> {
+    /// Constructs a empty `AdaptiveHeap` with the given policy, which measures the latency of `pop` with `clock`.
+    /// The heap starts with `PairingHeap`.
+    /// O(1) time.
+    pub fn with_clock(policy: AdaptivePolicy, clock: C) -> Self {
+        Self {
+            heap: AnyHeap::new(HeapKind::Pairing),
+            slots: Vec::new(),
+            free: Vec::new(),
+            stale: 0,
+            len: 0,
+            policy,
+            clock,
+            workload: Workload::default(),
+            migrations: 0,
+        }
+    }
+
+    /// Returns `true` if the heap contains no elements.
+    /// O(1) time.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of elements in the heap.
+    /// O(1) time.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the kind of the current backend.
+    /// O(1) time.
+    pub fn kind(&self) -> HeapKind {
+        self.heap.kind()
+    }
+
+    /// Returns the policy of the heap.
+    /// O(1) time.
+    pub fn policy(&self) -> &AdaptivePolicy {
+        &self.policy
+    }
+
+    /// Returns the operations observed in the current window.
+    /// O(1) time.
+    pub fn workload(&self) -> &Workload {
+        &self.workload
+    }
+
+    /// Returns the number of migrations between backends so far.
+    /// O(1) time.
+    pub fn migrations(&self) -> u64 {
+        self.migrations
+    }
+
+    /// Inserts a value into the heap.
+    /// Same time as the current backend, plus O(1) amortized time for migrations.
+    pub fn push(&mut self, value: T) {
+        self.heap.push(Entry {
+            value,
+            slot: NO_SLOT,
+            version: 0,
+        });
+        self.len += 1;
+        self.workload.pushes += 1;
+        self.end_of_operation();
+    }
+
+    /// Removes the minimum element from the heap and returns it, or `None` if it is empty.
+    /// Same time as the current backend, plus O(1) amortized time for migrations and stale entries.
+    pub fn pop(&mut self) -> Option<T> {
+        let start = self.clock.now();
+        let value = self.heap.pop().map(|entry| {
+            if entry.slot != NO_SLOT {
+                self.free_slot(entry.slot);
+            }
+            entry.value
+        });
+        self.discard_stale();
+        let latency = self.clock.now().saturating_sub(start);
+
+        if value.is_some() {
+            self.len -= 1;
+        }
+        self.workload.pops += 1;
+        if latency > self.policy.latency_limit {
+            self.workload.slow_pops += 1;
+        }
+        self.workload.max_pop_latency = self.workload.max_pop_latency.max(latency);
+        self.end_of_operation();
+        value
+    }
+
+    /// Returns a reference to the minimum element in the heap, or `None` if it is empty.
+    /// O(1) time.
+    pub fn peek(&self) -> Option<&T> {
+        self.heap.peek().map(|entry| &entry.value)
+    }
+
+    /// Returns a reference to the element of `handle`, or `None` if it has been removed.
+    /// O(1) time.
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.slots.get(handle.0)?.value.as_ref()
+    }
+
+    /// Removes the element of `handle` from the heap and returns it, or `None` if it has been removed.
+    /// It is counted as a pop in the workload.
+    /// O(1) amortized time, plus the time of the pops of the backend to discard the entry.
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        let value = self.slots.get_mut(handle.0)?.value.take()?;
+        self.free_slot(handle.0);
+        self.stale += 1;
+        self.discard_stale();
+        self.len -= 1;
+        self.workload.pops += 1;
+        self.end_of_operation();
+        Some(value)
+    }
+
+    /// Melds two heaps into a single heap.
+    /// The result keeps the backend, the policy, the clock, the workload and the handles of `heap1`.
+    /// Handles to the elements of `heap2` are invalidated.
+    /// Same time as the backend of `heap1`, plus O(m) time if the backends are different
+    /// or `heap2` has handed out handles.
+    pub fn meld(mut heap1: AdaptiveHeap<T, C>, heap2: AdaptiveHeap<T, C>) -> AdaptiveHeap<T, C> {
+        let AdaptiveHeap {
+            heap: heap2_heap,
+            slots: heap2_slots,
+            ..
+        } = heap2;
+        let heap2_heap = if heap2_slots.is_empty() {
+            heap2_heap
+        } else {
+            // Detach the entries of `heap2` from its table, and discard the stale ones.
+            let entries = Vec::from(heap2_heap)
+                .into_iter()
+                .filter(|entry| entry.is_current(&heap2_slots))
+                .map(|entry| Entry {
+                    slot: NO_SLOT,
+                    ..entry
+                })
+                .collect();
+            AnyHeap::from_vec(heap1.heap.kind(), entries)
+        };
+
+        let heap = core::mem::take(&mut heap1.heap);
+        heap1.heap = AnyHeap::meld(heap, heap2_heap);
+        heap1.len += heap2.len;
+        heap1.workload.melds += 1;
+        heap1.end_of_operation();
+        heap1
+    }
+
+    /// Returns an iterator that visits all elements in the heap, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.heap
+            .iter()
+            .filter(|entry| entry.is_current(&self.slots))
+            .map(|entry| &entry.value)
+    }
+
+    fn free_slot(&mut self, slot: usize) {
+        self.slots[slot].value = None;
+        self.slots[slot].version += 1;
+        self.free.push(slot);
+    }
+
+    // Pop the stale entries at the top of the backend, so that `peek` returns a current element.
+    fn discard_stale(&mut self) {
+        while let Some(entry) = self.heap.peek() {
+            if entry.is_current(&self.slots) {
+                break;
+            }
+            self.heap.pop();
+            self.stale -= 1;
+        }
+    }
+
+    // Choose the backend at the end of a window, which lasts at least `len` operations.
+    // The backend is also rebuilt if more than half of its entries are stale.
+    fn end_of_operation(&mut self) {
+        let workload = &self.workload;
+        let operations = workload.pushes + workload.pops + workload.melds;
+        let mut kind = self.heap.kind();
+        if operations >= self.policy.window.max(self.len) as u64 {
+            kind = self.policy.choose(workload);
+            self.workload = Workload::default();
+        }
+
+        if kind != self.heap.kind() || self.stale > self.len {
+            if kind != self.heap.kind() {
+                self.migrations += 1;
+            }
+            let heap = core::mem::take(&mut self.heap);
+            let entries = Vec::from(heap)
+                .into_iter()
+                .filter(|entry| entry.is_current(&self.slots))
+                .collect();
+            self.heap = AnyHeap::from_vec(kind, entries);
+            self.stale = 0;
+        }
+    }
+}
+
+impl<T: Ord + Clone, C: Clock> AdaptiveHeap<T, C> {
+    /// Inserts a value into the heap and returns a handle to it.
+    /// The handle stays valid across migrations.
+    /// Same time as the current backend, plus O(1) amortized time for migrations.
+    pub fn push_with_handle(&mut self, value: T) -> Handle {
+        let slot = self.free.pop().unwrap_or_else(|| {
+            self.slots.push(Slot {
+                value: None,
+                version: 0,
+            });
+            self.slots.len() - 1
+        });
+        self.slots[slot].value = Some(value.clone());
+        self.heap.push(Entry {
+            value,
+            slot,
+            version: self.slots[slot].version,
+        });
+        self.len += 1;
+        self.workload.pushes += 1;
+        self.end_of_operation();
+        Handle(slot)
+    }
+
+    /// Replaces the element of `handle` with `value`.
+    /// A new entry is pushed to the backend, so it is counted as a push in the workload.
+    /// Same time as `push` of the current backend, plus O(1) amortized time to discard the old entry.
+    ///
+    /// # Panics
+    /// Panics if the element has been removed or `value` is greater than the current element.
+    pub fn decrease_key(&mut self, handle: Handle, value: T) {
+        let slot = self
+            .slots
+            .get_mut(handle.0)
+            .filter(|slot| slot.value.is_some())
+            .expect("decrease_key: invalid handle");
+        assert!(
+            Some(&value) <= slot.value.as_ref(),
+            "decrease_key: new value is greater than the current value"
+        );
+        slot.value = Some(value.clone());
+        slot.version += 1;
+        let version = slot.version;
+        self.heap.push(Entry {
+            value,
+            slot: handle.0,
+            version,
+        });
+        self.stale += 1;
+        self.discard_stale();
+        self.workload.pushes += 1;
+        self.end_of_operation();
+    }
+}
+
+impl<T: Ord, C: Clock + Default> Default for AdaptiveHeap<T, C> {
+    fn default() -> Self {
+        Self::with_clock(AdaptivePolicy::default(), C::default())
+    }
+}
+
+#[rustfmt::skip]
+impl<T: Ord, C: Clock + Default> MeldableHeap<T> for AdaptiveHeap<T, C> {
+    fn is_empty(&self) -> bool { self.is_empty() }
+    fn push(&mut self, value: T) { self.push(value) }
+    fn pop(&mut self) -> Option<T> { self.pop() }
+    fn peek(&self) -> Option<&T> { self.peek() }
+    fn meld(heap1: Self, heap2: Self) -> Self { Self::meld(heap1, heap2) }
+}
+
+#[rustfmt::skip]
+impl<T: Ord + Clone, C: Clock + Default> AddressableHeap<T> for AdaptiveHeap<T, C> {
+    fn push_with_handle(&mut self, value: T) -> Handle { self.push_with_handle(value) }
+    fn get(&self, handle: Handle) -> Option<&T> { self.get(handle) }
+    fn decrease_key(&mut self, handle: Handle, value: T) { self.decrease_key(handle, value) }
+    fn remove(&mut self, handle: Handle) -> Option<T> { self.remove(handle) }
+}
+
+const NO_SLOT: usize = usize::MAX;
+
+// An entry of the backend. Only the values are compared.
+struct Entry<T> {
+    value: T,
+    // The slot of the handle, or `NO_SLOT` if the entry has no handle
+    slot: usize,
+    // The version of the slot when the entry was pushed
+    version: usize,
+}
+
+impl<T> Entry<T> {
+    // Returns `false` if the entry has been replaced by `decrease_key` or removed by `remove`.
+    fn is_current(&self, slots: &[Slot<T>]) -> bool {
+        self.slot == NO_SLOT || slots[self.slot].version == self.version
+    }
+}
+
+impl<T: Ord> PartialEq for Entry<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Ord> Eq for Entry<T> {}
+
+impl<T: Ord> PartialOrd for Entry<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Ord> Ord for Entry<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+struct Slot<T> {
+    // A copy of the current element, or `None` if the slot is free
+    value: Option<T>,
+    // Incremented whenever the entry of the slot in the backend becomes stale
+    version: usize,
+}
+
+/// `AdaptivePolicy` determines when [`AdaptiveHeap`] migrates between its backends.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct AdaptivePolicy {
+    /// The minimum number of operations in a window. Default: 1024.
+    pub window: usize,
+    /// Use `PairingHeap` if the fraction of pushes and melds in a window is at least this value. Default: 0.6.
+    pub pairing_ratio: f64,
+    /// Use `LeftistHeap` if the fraction of pushes and melds in a window is at most this value. Default: 0.4.
+    pub leftist_ratio: f64,
+    /// A pop is slow if its latency measured by the clock is greater than this value. Default: `u64::MAX`.
+    pub latency_limit: u64,
+    /// Use `LeftistHeap` if the fraction of slow pops in a window is greater than this value. Default: 0.01.
+    pub tail_ratio: f64,
+}
+
+impl AdaptivePolicy {
+    /// Returns the backend for the workload of a window.
+    pub fn choose(&self, workload: &Workload) -> HeapKind {
+        let pops = workload.pops as f64;
+        if workload.slow_pops as f64 > self.tail_ratio * pops {
+            return HeapKind::Leftist;
+        }
+
+        let updates = (workload.pushes + workload.melds) as f64;
+        let ratio = updates / (updates + pops);
+        if ratio >= self.pairing_ratio {
+            HeapKind::Pairing
+        } else if ratio <= self.leftist_ratio {
+            HeapKind::Leftist
+        } else {
+            HeapKind::Skew
+        }
+    }
+}
+
+impl Default for AdaptivePolicy {
+    fn default() -> Self {
+        Self {
+            window: 1024,
+            pairing_ratio: 0.6,
+            leftist_ratio: 0.4,
+            latency_limit: u64::MAX,
+            tail_ratio: 0.01,
+        }
+    }
+}
+
+/// `Workload` is the numbers of operations observed by [`AdaptiveHeap`] in a window.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct Workload {
+    /// The number of pushes.
+    pub pushes: u64,
+    /// The number of pops, including pops from an empty heap.
+    pub pops: u64,
+    /// The number of melds.
+    pub melds: u64,
+    /// The number of pops slower than [`AdaptivePolicy::latency_limit`].
+    pub slow_pops: u64,
+    /// The maximum latency of the pops.
+    pub max_pop_latency: u64,
+}
+
+/// `Clock` measures the latency of `pop` in [`AdaptiveHeap`].
+///
+/// The unit of time is up to the implementation, e.g. nanoseconds or CPU cycles.
+/// Closures returning `u64` implement this trait.
+pub trait Clock {
+    /// Returns the current time.
+    fn now(&mut self) -> u64;
+}
+
+impl<F: FnMut() -> u64> Clock for F {
+    fn now(&mut self) -> u64 {
+        self()
+    }
+}
+
+/// `NoClock` is a [`Clock`] which always returns 0, so that the latency is not taken into account.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct NoClock;
+
+impl Clock for NoClock {
+    fn now(&mut self) -> u64 {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BinaryHeap;
+    use alloc::vec::Vec;
+    use core::cmp::Reverse;
+
+    use crate::testing::{self, ModelConfig};
+    use crate::{AdaptiveHeap, AdaptivePolicy, HeapKind};
+
+    #[test]
+    fn basic_test() {
+        testing::check_basic::<AdaptiveHeap<u32>>();
+    }
+
+    #[test]
+    #[cfg_attr(feature = "debug-invariants", ignore)]
+    fn large_drop_test() {
+        testing::check_deep::<AdaptiveHeap<u32>>(1000000);
+    }
+
+    #[test]
+    fn randomized_test() {
+        let config = ModelConfig {
+            seed: rand::random(),
+            ..ModelConfig::default()
+        };
+        testing::check_model::<AdaptiveHeap<u32>>(&config);
+    }
+
+    #[test]
+    fn addressable_test() {
+        let config = ModelConfig {
+            seed: rand::random(),
+            ..ModelConfig::default()
+        };
+        testing::check_addressable::<AdaptiveHeap<u32>>(&config);
+    }
+
+    #[test]
+    fn handle_migration_test() {
+        let policy = AdaptivePolicy {
+            window: 10,
+            ..AdaptivePolicy::default()
+        };
+        let mut heap = AdaptiveHeap::with_policy(policy);
+        let handles: Vec<_> = (0..100).map(|x| heap.push_with_handle(x + 1000)).collect();
+        assert_eq!(heap.kind(), HeapKind::Pairing);
+
+        // A pop-heavy workload migrates the heap to `LeftistHeap`.
+        for x in 0..60 {
+            assert_eq!(heap.pop(), Some(x + 1000));
+        }
+        assert_eq!(heap.kind(), HeapKind::Leftist);
+
+        // A balanced workload of `decrease_key` and `pop` migrates the heap to `SkewHeap`.
+        for (i, &handle) in handles.iter().enumerate().take(90).skip(60) {
+            heap.decrease_key(handle, i as u32);
+            assert_eq!(heap.pop(), Some(i as u32));
+        }
+        assert_eq!(heap.kind(), HeapKind::Skew);
+        assert_eq!(heap.migrations(), 2);
+
+        // The handles of the remaining elements still refer to them after the migrations.
+        for (i, &handle) in handles.iter().enumerate() {
+            let expected = (i >= 90).then_some(i as u32 + 1000);
+            assert_eq!(heap.get(handle), expected.as_ref());
+        }
+        assert_eq!(heap.remove(handles[99]), Some(1099));
+        heap.decrease_key(handles[95], 0);
+        assert_eq!(heap.pop(), Some(0));
+        assert_eq!(heap.len(), 8);
+    }
+
+    #[test]
+    fn migration_test() {
+        let policy = AdaptivePolicy {
+            window: 100,
+            ..AdaptivePolicy::default()
+        };
+        let mut heap = AdaptiveHeap::with_policy(policy);
+
+        // Push-heavy workload
+        for x in 0..1000 {
+            heap.push(x);
+        }
+        assert_eq!(heap.kind(), HeapKind::Pairing);
+        assert_eq!(heap.migrations(), 0);
+
+        // Balanced workload
+        for x in 1000..3000 {
+            heap.push(x);
+            heap.pop();
+        }
+        assert_eq!(heap.kind(), HeapKind::Skew);
+
+        // Pop-heavy workload
+        for x in 2000..3000 {
+            assert_eq!(heap.pop(), Some(x));
+        }
+        assert_eq!(heap.kind(), HeapKind::Leftist);
+        assert_eq!(heap.migrations(), 2);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn latency_test() {
+        // Each pop takes one tick, which exceeds the limit.
+        let mut time = 0;
+        let clock = move || {
+            time += 1;
+            time
+        };
+        let policy = AdaptivePolicy {
+            window: 100,
+            latency_limit: 0,
+            ..AdaptivePolicy::default()
+        };
+        let mut heap = AdaptiveHeap::with_clock(policy, clock);
+        for x in 0..100 {
+            heap.push(x);
+        }
+        assert_eq!(heap.kind(), HeapKind::Pairing);
+
+        // A push-heavy workload with slow pops
+        for x in 0..100 {
+            for _ in 0..10 {
+                heap.push(x);
+            }
+            heap.pop();
+        }
+        assert_eq!(heap.kind(), HeapKind::Leftist);
+    }
+
+    #[test]
+    fn randomized_migration_test() {
+        for _ in 0..1000 {
+            let policy = AdaptivePolicy {
+                window: 10,
+                ..AdaptivePolicy::default()
+            };
+            let mut heap = AdaptiveHeap::with_policy(policy);
+            // BinaryHeap is max-heap. So, we need to push Reverse(x) to make it min-heap.
+            let mut expected = BinaryHeap::<Reverse<u32>>::new();
+            let bias = rand::random::<u32>() % 4;
+            for _ in 0..100 {
+                match (rand::random::<u32>() % 6).saturating_sub(bias) {
+                    0 => {
+                        let actual_x = heap.pop();
+                        let expected_x = expected.pop().map(|r| r.0);
+                        assert_eq!(actual_x, expected_x);
+                    }
+                    1 => {
+                        let mut other = AdaptiveHeap::with_policy(policy);
+                        for _ in 0..rand::random::<u32>() % 10 {
+                            let x = rand::random::<u32>() % 100;
+                            other.push(x);
+                            expected.push(Reverse(x));
+                        }
+                        heap = AdaptiveHeap::meld(heap, other);
+                    }
+                    _ => {
+                        let x = rand::random::<u32>() % 100;
+                        heap.push(x);
+                        expected.push(Reverse(x));
+                    }
+                }
+                assert_eq!(expected.len(), heap.len());
+                assert_eq!(expected.peek().map(|r| r.0), heap.peek().copied());
+            }
+        }
+    }
+}
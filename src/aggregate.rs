@@ -0,0 +1,92 @@
+#![forbid(unsafe_code)]
+
+use core::ops::Add;
+
+/// `Aggregate` is a commutative monoid over the elements, which can be attached to
+/// [`LeftistHeap`](crate::LeftistHeap) and [`SkewHeap`](crate::SkewHeap).
+///
+/// Each node stores the aggregate of its subtree, which is maintained through `push`, `pop` and `meld`,
+/// so that the aggregate of a whole heap is available in O(1) time.
+/// `combine` must be associative and commutative, and `identity` must be its identity element,
+/// because melds regroup the elements in arbitrary order.
+pub trait Aggregate<T> {
+    /// The type of aggregated values.
+    type Value: Clone;
+
+    /// Returns the aggregate of no elements.
+    fn identity() -> Self::Value;
+
+    /// Returns the aggregate of a single element.
+    fn lift(value: &T) -> Self::Value;
+
+    /// Combines the aggregates of two disjoint sets of elements.
+    fn combine(a: &Self::Value, b: &Self::Value) -> Self::Value;
+}
+
+/// The default aggregate, which stores nothing.
+impl<T> Aggregate<T> for () {
+    type Value = ();
+
+    fn identity() {}
+
+    fn lift(_value: &T) {}
+
+    fn combine(_a: &(), _b: &()) {}
+}
+
+/// A pair of aggregates, which computes both of them.
+impl<T, A: Aggregate<T>, B: Aggregate<T>> Aggregate<T> for (A, B) {
+    type Value = (A::Value, B::Value);
+
+    fn identity() -> Self::Value {
+        (A::identity(), B::identity())
+    }
+
+    fn lift(value: &T) -> Self::Value {
+        (A::lift(value), B::lift(value))
+    }
+
+    fn combine(a: &Self::Value, b: &Self::Value) -> Self::Value {
+        (A::combine(&a.0, &b.0), B::combine(&a.1, &b.1))
+    }
+}
+
+/// `Count` is an [`Aggregate`] which counts the elements.
+#[derive(Clone, Copy, Debug)]
+pub struct Count;
+
+impl<T> Aggregate<T> for Count {
+    type Value = usize;
+
+    fn identity() -> usize {
+        0
+    }
+
+    fn lift(_value: &T) -> usize {
+        1
+    }
+
+    fn combine(a: &usize, b: &usize) -> usize {
+        a + b
+    }
+}
+
+/// `Sum` is an [`Aggregate`] which sums up the elements. The sum of no elements is `T::default()`.
+#[derive(Clone, Copy, Debug)]
+pub struct Sum;
+
+impl<T: Clone + Default + Add<Output = T>> Aggregate<T> for Sum {
+    type Value = T;
+
+    fn identity() -> T {
+        T::default()
+    }
+
+    fn lift(value: &T) -> T {
+        value.clone()
+    }
+
+    fn combine(a: &T, b: &T) -> T {
+        a.clone() + b.clone()
+    }
+}
@@ -0,0 +1,338 @@
+#![forbid(unsafe_code)]
+
+use alloc::vec::Vec;
+
+use crate::{HeapStats, InvariantError, LeftistHeap, MeldableHeap, PairingHeap, SkewHeap};
+
+/// `HeapKind` selects the implementation of [`AnyHeap`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum HeapKind {
+    /// [`PairingHeap`]
+    #[default]
+    Pairing,
+    /// [`LeftistHeap`]
+    Leftist,
+    /// [`SkewHeap`]
+    Skew,
+}
+
+/// `AnyHeap` is a priority queue whose implementation is selected at runtime.
+/// `AnyHeap` is a **min-heap**, which means that the minimum element is popped first.
+///
+/// Each method dispatches to the underlying heap, so it takes the same time as the selected implementation.
+/// The implementation can be changed later with [`AnyHeap::convert`].
+pub enum AnyHeap<T: Ord> {
+    Pairing(PairingHeap<T>),
+    Leftist(LeftistHeap<T>),
+    Skew(SkewHeap<T>),
+}
+
+impl<T: Ord> AnyHeap<T> {
+    /// Constructs a empty `AnyHeap` of the given kind.
+    /// O(1) time.
+    pub fn new(kind: HeapKind) -> Self {
+        match kind {
+            HeapKind::Pairing => AnyHeap::Pairing(PairingHeap::new()),
+            HeapKind::Leftist => AnyHeap::Leftist(LeftistHeap::new()),
+            HeapKind::Skew => AnyHeap::Skew(SkewHeap::new()),
+        }
+    }
+
+    /// Returns the kind of the underlying heap.
+    /// O(1) time.
+    pub fn kind(&self) -> HeapKind {
+        match self {
+            AnyHeap::Pairing(_) => HeapKind::Pairing,
+            AnyHeap::Leftist(_) => HeapKind::Leftist,
+            AnyHeap::Skew(_) => HeapKind::Skew,
+        }
+    }
+
+    /// Returns `true` if the heap contains no elements.
+    /// O(1) time.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            AnyHeap::Pairing(heap) => heap.is_empty(),
+            AnyHeap::Leftist(heap) => heap.is_empty(),
+            AnyHeap::Skew(heap) => heap.is_empty(),
+        }
+    }
+
+    /// Inserts a value into the heap.
+    pub fn push(&mut self, value: T) {
+        match self {
+            AnyHeap::Pairing(heap) => heap.push(value),
+            AnyHeap::Leftist(heap) => heap.push(value),
+            AnyHeap::Skew(heap) => heap.push(value),
+        }
+    }
+
+    /// Removes the minimum element from the heap and returns it, or `None` if it is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        match self {
+            AnyHeap::Pairing(heap) => heap.pop(),
+            AnyHeap::Leftist(heap) => heap.pop(),
+            AnyHeap::Skew(heap) => heap.pop(),
+        }
+    }
+
+    /// Returns a reference to the minimum element in the heap, or `None` if it is empty.
+    pub fn peek(&self) -> Option<&T> {
+        match self {
+            AnyHeap::Pairing(heap) => heap.peek(),
+            AnyHeap::Leftist(heap) => heap.peek(),
+            AnyHeap::Skew(heap) => heap.peek(),
+        }
+    }
+
+    /// Melds two heaps into a single heap of the kind of `heap1`.
+    /// If the kinds are different, `heap2` is converted first, which takes O(m) time.
+    pub fn meld(heap1: AnyHeap<T>, heap2: AnyHeap<T>) -> AnyHeap<T> {
+        let kind = heap1.kind();
+        match (heap1, heap2.convert(kind)) {
+            (AnyHeap::Pairing(h1), AnyHeap::Pairing(h2)) => {
+                AnyHeap::Pairing(PairingHeap::meld(h1, h2))
+            }
+            (AnyHeap::Leftist(h1), AnyHeap::Leftist(h2)) => {
+                AnyHeap::Leftist(LeftistHeap::meld(h1, h2))
+            }
+            (AnyHeap::Skew(h1), AnyHeap::Skew(h2)) => AnyHeap::Skew(SkewHeap::meld(h1, h2)),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Converts the heap into the given kind.
+    /// O(n) time, or O(1) time if the kind is not changed.
+    pub fn convert(self, kind: HeapKind) -> AnyHeap<T> {
+        if self.kind() == kind {
+            return self;
+        }
+        Self::from_vec(kind, Vec::from(self))
+    }
+
+    /// Builds a heap of the given kind from the elements of a vector.
+    /// O(n) time.
+    pub fn from_vec(kind: HeapKind, values: Vec<T>) -> AnyHeap<T> {
+        match kind {
+            HeapKind::Pairing => AnyHeap::Pairing(PairingHeap::from(values)),
+            HeapKind::Leftist => AnyHeap::Leftist(LeftistHeap::from(values)),
+            HeapKind::Skew => AnyHeap::Skew(SkewHeap::from(values)),
+        }
+    }
+
+    /// Returns statistics of the shape of the underlying heap.
+    /// O(n) time.
+    pub fn stats(&self) -> HeapStats {
+        match self {
+            AnyHeap::Pairing(heap) => heap.stats(),
+            AnyHeap::Leftist(heap) => heap.stats(),
+            AnyHeap::Skew(heap) => heap.stats(),
+        }
+    }
+
+    /// Returns the operation counters of the underlying heap.
+    /// O(1) time.
+    #[cfg(feature = "instrumentation")]
+    pub fn counters(&self) -> crate::OpCounters {
+        match self {
+            AnyHeap::Pairing(heap) => heap.counters(),
+            AnyHeap::Leftist(heap) => heap.counters(),
+            AnyHeap::Skew(heap) => heap.counters(),
+        }
+    }
+
+    /// Resets the operation counters of the underlying heap to zero.
+    /// O(1) time.
+    #[cfg(feature = "instrumentation")]
+    pub fn reset_counters(&mut self) {
+        match self {
+            AnyHeap::Pairing(heap) => heap.reset_counters(),
+            AnyHeap::Leftist(heap) => heap.reset_counters(),
+            AnyHeap::Skew(heap) => heap.reset_counters(),
+        }
+    }
+
+    /// Checks the invariants of the underlying heap, and returns the first violation found.
+    /// O(n) time.
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        match self {
+            AnyHeap::Pairing(heap) => heap.validate(),
+            AnyHeap::Leftist(heap) => heap.validate(),
+            AnyHeap::Skew(heap) => heap.validate(),
+        }
+    }
+
+    /// Returns an iterator that visits all elements in the heap, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let (pairing, leftist, skew) = match self {
+            AnyHeap::Pairing(heap) => (Some(heap.iter()), None, None),
+            AnyHeap::Leftist(heap) => (None, Some(heap.iter()), None),
+            AnyHeap::Skew(heap) => (None, None, Some(heap.iter())),
+        };
+        let pairing = pairing.into_iter().flatten();
+        let leftist = leftist.into_iter().flatten();
+        let skew = skew.into_iter().flatten();
+        pairing.chain(leftist).chain(skew)
+    }
+}
+
+impl<T: Ord> Default for AnyHeap<T> {
+    fn default() -> Self {
+        Self::new(HeapKind::default())
+    }
+}
+
+#[rustfmt::skip]
+impl<T: Ord> MeldableHeap<T> for AnyHeap<T> {
+    fn is_empty(&self) -> bool { self.is_empty() }
+    fn push(&mut self, value: T) { self.push(value) }
+    fn pop(&mut self) -> Option<T> { self.pop() }
+    fn peek(&self) -> Option<&T> { self.peek() }
+    fn meld(heap1: Self, heap2: Self) -> Self { Self::meld(heap1, heap2) }
+}
+
+impl<T: Ord> From<PairingHeap<T>> for AnyHeap<T> {
+    fn from(heap: PairingHeap<T>) -> Self {
+        AnyHeap::Pairing(heap)
+    }
+}
+
+impl<T: Ord> From<LeftistHeap<T>> for AnyHeap<T> {
+    fn from(heap: LeftistHeap<T>) -> Self {
+        AnyHeap::Leftist(heap)
+    }
+}
+
+impl<T: Ord> From<SkewHeap<T>> for AnyHeap<T> {
+    fn from(heap: SkewHeap<T>) -> Self {
+        AnyHeap::Skew(heap)
+    }
+}
+
+impl<T: Ord> From<AnyHeap<T>> for Vec<T> {
+    /// Returns the elements of the heap in arbitrary order.
+    /// O(n) time.
+    fn from(heap: AnyHeap<T>) -> Self {
+        match heap {
+            AnyHeap::Pairing(heap) => Vec::from(heap),
+            AnyHeap::Leftist(heap) => Vec::from(heap),
+            AnyHeap::Skew(heap) => Vec::from(heap),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BinaryHeap;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use core::cmp::Reverse;
+
+    use crate::testing::{self, ModelConfig};
+    use crate::{AnyHeap, HeapKind, LeftistHeap, PairingHeap, SkewHeap};
+
+    #[test]
+    fn basic_test() {
+        testing::check_basic::<AnyHeap<u32>>();
+    }
+
+    #[test]
+    #[cfg_attr(feature = "debug-invariants", ignore)]
+    fn large_drop_test() {
+        testing::check_deep::<AnyHeap<u32>>(1000000);
+    }
+
+    #[test]
+    fn randomized_test() {
+        let config = ModelConfig {
+            seed: rand::random(),
+            ..ModelConfig::default()
+        };
+        testing::check_model::<AnyHeap<u32>>(&config);
+    }
+
+    const KINDS: [HeapKind; 3] = [HeapKind::Pairing, HeapKind::Leftist, HeapKind::Skew];
+
+    #[test]
+    #[cfg_attr(feature = "debug-invariants", ignore)]
+    fn conversion_test() {
+        // Sorted pushes make deep trees, so that the conversions must not recurse.
+        let n = 1000000;
+        let mut heap = SkewHeap::new();
+        for x in (0..n).rev() {
+            heap.push(x);
+        }
+        heap.pop();
+        let heap = LeftistHeap::from(heap);
+        let heap: PairingHeap<_> = PairingHeap::from(heap);
+        let mut heap = SkewHeap::from(heap);
+        assert_eq!(heap.pop(), Some(1));
+
+        let mut values = Vec::from(heap);
+        values.sort();
+        assert_eq!(values, (2..n).collect::<Vec<_>>());
+
+        let mut heap = LeftistHeap::from(values);
+        for x in 2..n {
+            assert_eq!(heap.pop(), Some(x));
+        }
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn binary_heap_test() {
+        let values = [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5, 8, 9, 7, 9];
+        let binary_heap: BinaryHeap<_> = values.iter().copied().map(Reverse).collect();
+        let mut heaps = [
+            AnyHeap::from(PairingHeap::from(binary_heap.clone())),
+            AnyHeap::from(LeftistHeap::from(binary_heap.clone())),
+            AnyHeap::from(SkewHeap::from(binary_heap)),
+        ];
+        for heap in &mut heaps {
+            let actual: Vec<_> = core::iter::from_fn(|| heap.pop()).collect();
+            let expected = vec![1, 1, 2, 3, 3, 4, 5, 5, 5, 6, 7, 8, 9, 9, 9];
+            assert_eq!(expected, actual);
+        }
+    }
+
+    #[test]
+    fn randomized_convert_test() {
+        for _ in 0..1000 {
+            let mut heap = AnyHeap::new(KINDS[rand::random::<usize>() % 3]);
+            // BinaryHeap is max-heap. So, we need to push Reverse(x) to make it min-heap.
+            let mut expected = BinaryHeap::new();
+            for _ in 0..100 {
+                match rand::random::<u32>() % 4 {
+                    0 => {
+                        let x = rand::random::<u32>() % 100;
+                        heap.push(x);
+                        expected.push(Reverse(x));
+                    }
+                    1 => {
+                        let actual_x = heap.pop();
+                        let expected_x = expected.pop().map(|r| r.0);
+                        assert_eq!(actual_x, expected_x);
+                    }
+                    2 => {
+                        let mut other = AnyHeap::new(KINDS[rand::random::<usize>() % 3]);
+                        for _ in 0..rand::random::<u32>() % 10 {
+                            let x = rand::random::<u32>() % 100;
+                            other.push(x);
+                            expected.push(Reverse(x));
+                        }
+                        let kind = heap.kind();
+                        heap = AnyHeap::meld(heap, other);
+                        assert_eq!(heap.kind(), kind);
+                    }
+                    3 => {
+                        let kind = KINDS[rand::random::<usize>() % 3];
+                        heap = heap.convert(kind);
+                        assert_eq!(heap.kind(), kind);
+                    }
+                    _ => unreachable!(),
+                }
+                assert_eq!(expected.peek().map(|r| r.0), heap.peek().copied());
+            }
+        }
+    }
+}
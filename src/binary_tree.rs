@@ -0,0 +1,176 @@
+#![forbid(unsafe_code)]
+
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::{Aggregate, Counters};
+
+// `BinaryNode` is a node of a heap-ordered binary tree whose subtrees keep an `Aggregate`,
+// i.e. a node of `LeftistHeap` or `SkewHeap`.
+// The functions below depend only on the heap order and the left and right children,
+// so they are shared by both heaps, and the shape of the trees is left to `meld` of each node.
+// They ignore pending `Lazy` updates, so they are implemented only for the nodes without them.
+pub(crate) trait BinaryNode<T: Ord, A: Aggregate<T>>: Sized {
+    fn value(&self) -> &T;
+
+    fn value_mut(&mut self) -> &mut T;
+
+    // The aggregate of the subtree
+    fn agg(&self) -> &A::Value;
+
+    fn agg_mut(&mut self) -> &mut A::Value;
+
+    fn left(&self) -> Option<&Self>;
+
+    fn right(&self) -> Option<&Self>;
+
+    fn children_mut(&mut self) -> (&mut Option<Box<Self>>, &mut Option<Box<Self>>);
+
+    // Make the node a singleton again after its children are taken, e.g. reset its rank and aggregate.
+    fn reset(&mut self);
+
+    fn meld(
+        root1: Option<Box<Self>>,
+        root2: Option<Box<Self>>,
+        counters: &mut Counters,
+    ) -> Option<Box<Self>>;
+}
+
+// Meld the trees in pairs round by round, so that the total cost is O(n) rather than O(n log n)
+// for n singletons.
+pub(crate) fn meld_all<T: Ord, A: Aggregate<T>, N: BinaryNode<T, A>>(
+    mut roots: Vec<Option<Box<N>>>,
+    counters: &mut Counters,
+) -> Option<Box<N>> {
+    while roots.len() > 1 {
+        let len = roots.len();
+        for i in 0..len / 2 {
+            let root1 = roots[2 * i].take();
+            let root2 = roots[2 * i + 1].take();
+            roots[i] = N::meld(root1, root2, counters);
+        }
+        if len % 2 == 1 {
+            roots[len / 2] = roots[len - 1].take();
+        }
+        roots.truncate(len.div_ceil(2));
+    }
+    roots.pop().flatten()
+}
+
+// Recompute the aggregates of all nodes after their values are rewritten.
+// The nodes are detached in preorder and reattached in reverse order,
+// so that the children of a node are complete when its aggregate is recomputed.
+pub(crate) fn update_aggregates<T: Ord, A: Aggregate<T>, N: BinaryNode<T, A>>(
+    root: &mut Option<Box<N>>,
+) {
+    // A zero-sized aggregate such as `()` has only one value, so there is nothing to recompute.
+    if size_of::<A::Value>() == 0 {
+        return;
+    }
+    let mut nodes = Vec::new();
+    let mut stack = Vec::new();
+    stack.extend(root.take());
+    while let Some(mut node) = stack.pop() {
+        let (left, right) = node.children_mut();
+        let (left, right) = (left.take(), right.take());
+        let shape = (left.is_some(), right.is_some());
+        stack.extend(right);
+        stack.extend(left);
+        nodes.push((node, shape));
+    }
+
+    // The left child of a node is completed just after the right child, so it is at the top of `done`.
+    let mut done: Vec<Box<N>> = Vec::new();
+    while let Some((mut node, (has_left, has_right))) = nodes.pop() {
+        let mut agg = A::lift(node.value());
+        let left = has_left.then(|| done.pop().unwrap());
+        let right = has_right.then(|| done.pop().unwrap());
+        for child in left.iter().chain(&right) {
+            agg = A::combine(&agg, child.agg());
+        }
+        *node.agg_mut() = agg;
+        let (node_left, node_right) = node.children_mut();
+        *node_left = left;
+        *node_right = right;
+        done.push(node);
+    }
+    *root = done.pop();
+}
+
+// Visit the elements not greater than `bound`, skipping the subtrees whose roots are greater than `bound`.
+pub(crate) fn iter_below<'a, T: Ord, A: Aggregate<T>, N: BinaryNode<T, A>>(
+    root: Option<&'a N>,
+    bound: &'a T,
+) -> impl Iterator<Item = &'a T> + 'a {
+    let mut stack = Vec::new();
+    stack.extend(root);
+    core::iter::from_fn(move || loop {
+        let node = stack.pop()?;
+        if node.value() <= bound {
+            stack.extend(node.left());
+            stack.extend(node.right());
+            return Some(node.value());
+        }
+    })
+}
+
+// Detach the nodes not greater than `bound` by a depth-first search which stops at the nodes greater than `bound`.
+// `root` is rebuilt from the rest, and the tree of the detached nodes is returned.
+pub(crate) fn split_off_below<T: Ord, A: Aggregate<T>, N: BinaryNode<T, A>>(
+    root: &mut Option<Box<N>>,
+    bound: &T,
+    counters: &mut Counters,
+) -> Option<Box<N>> {
+    let mut below = Vec::new();
+    let mut above = Vec::new();
+    let mut stack = Vec::new();
+    stack.extend(root.take());
+    while let Some(mut node) = stack.pop() {
+        if node.value() > bound {
+            above.push(Some(node));
+            continue;
+        }
+        let (left, right) = node.children_mut();
+        stack.extend(left.take());
+        stack.extend(right.take());
+        node.reset();
+        below.push(Some(node));
+    }
+    *root = meld_all(above, counters);
+    meld_all(below, counters)
+}
+
+// Rewrite all elements in place with `f`, which must preserve their order, keeping the shape of the tree.
+pub(crate) fn map_monotone<T: Ord, A: Aggregate<T>, N: BinaryNode<T, A>>(
+    root: &mut Option<Box<N>>,
+    mut f: impl FnMut(&mut T),
+) {
+    let mut stack = Vec::new();
+    stack.extend(root.as_deref_mut());
+    while let Some(node) = stack.pop() {
+        f(node.value_mut());
+        let (left, right) = node.children_mut();
+        stack.extend(left.as_deref_mut());
+        stack.extend(right.as_deref_mut());
+    }
+    update_aggregates(root);
+}
+
+// Rewrite all elements with `f`, and rebuild the tree by melding the detached nodes in pairs.
+pub(crate) fn reprioritize<T: Ord, A: Aggregate<T>, N: BinaryNode<T, A>>(
+    root: &mut Option<Box<N>>,
+    mut f: impl FnMut(&mut T),
+    counters: &mut Counters,
+) {
+    let mut roots = Vec::new();
+    let mut stack = Vec::new();
+    stack.extend(root.take());
+    while let Some(mut node) = stack.pop() {
+        let (left, right) = node.children_mut();
+        stack.extend(left.take());
+        stack.extend(right.take());
+        f(node.value_mut());
+        node.reset();
+        roots.push(Some(node));
+    }
+    *root = meld_all(roots, counters);
+}
@@ -0,0 +1,305 @@
+#![forbid(unsafe_code)]
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::MeldableHeap;
+
+/// `BottomUpSkewHeap` is a priority queue implemented with bottom-up skew heap.
+/// `BottomUpSkewHeap` is a **min-heap**, which means that the minimum element is popped first.
+///
+/// Unlike `SkewHeap`, `meld` merges the right paths of two heaps from the bottom, and stops as soon as
+/// one of the paths is exhausted. Each right path is stored as a ring: `up` of a node points to its parent
+/// on the path, and `up` of the top node points to the bottom node of the path.
+///
+/// Nodes are stored in a vector and linked by indices, so that the rings are written in safe Rust.
+/// Linking two heaps takes O(1) amortized time as in the paper, but the nodes of two heaps are in different vectors,
+/// so [`meld`](Self::meld) moves the nodes of the smaller heap and takes O(min(n, m)) time rather than O(1).
+/// The vector is compacted when fewer than a quarter of its slots are in use, so that the free slots
+/// do not slow down `meld` and `iter` after the heap shrinks.
+///
+/// # Reference
+/// Sleator, Daniel Dominic; Tarjan, Robert Endre (1986).
+/// ["Self-Adjusting Heaps"][Sleator-Tarjan-1986]
+///
+/// [Sleator-Tarjan-1986]: https://www.cs.cmu.edu/~sleator/papers/Adjusting-Heaps.htm
+pub struct BottomUpSkewHeap<T: Ord> {
+    nodes: Vec<Node<T>>,
+    free: Vec<usize>,
+    root: Option<usize>,
+    len: usize,
+}
+
+impl<T: Ord> BottomUpSkewHeap<T> {
+    /// Constructs a empty `BottomUpSkewHeap`.
+    /// O(1) time.
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            root: None,
+            len: 0,
+        }
+    }
+
+    /// Returns `true` if the heap contains no elements.
+    /// O(1) time.
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Returns the number of elements in the heap.
+    /// O(1) time.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Inserts a value into the heap.
+    /// O(1) amortized time.
+    pub fn push(&mut self, value: T) {
+        let node = self.alloc(value);
+        self.root = self.meld_nodes(self.root, Some(node));
+        self.len += 1;
+    }
+
+    /// Removes the minimum element from the heap and returns it, or `None` if it is empty.
+    /// O(log n) amortized time.
+    pub fn pop(&mut self) -> Option<T> {
+        let root = self.root?;
+        let left = self.nodes[root].left;
+        let right = self.nodes[root].right;
+
+        // The right child becomes the top of the rest of the right path.
+        if let Some(right) = right {
+            self.nodes[right].up = self.nodes[root].up;
+        }
+        self.root = self.meld_nodes(left, right);
+
+        self.free.push(root);
+        self.len -= 1;
+        let value = self.nodes[root].value.take();
+        if self.nodes.len() > 4 * self.len {
+            self.compact();
+        }
+        value
+    }
+
+    /// Returns a reference to the minimum element in the heap, or `None` if it is empty.
+    /// O(1) time.
+    pub fn peek(&self) -> Option<&T> {
+        self.root.map(|root| self.value(root))
+    }
+
+    /// Melds two heaps into a single heap.
+    /// O(min(n, m)) time to move the nodes of the smaller heap into the storage of the larger one,
+    /// where n and m are the numbers of elements, plus O(1) amortized time to link the heaps.
+    pub fn meld(
+        mut heap1: BottomUpSkewHeap<T>,
+        mut heap2: BottomUpSkewHeap<T>,
+    ) -> BottomUpSkewHeap<T> {
+        if heap1.len < heap2.len {
+            core::mem::swap(&mut heap1, &mut heap2);
+        }
+
+        let root2 = heap1.move_from(&mut heap2);
+        heap1.root = heap1.meld_nodes(heap1.root, root2);
+        heap1.len += heap2.len;
+        heap1
+    }
+
+    /// Returns an iterator that visits all elements in the heap, in arbitrary order.
+    /// O(n) time, because the vector is compacted when most of its slots are free.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.nodes.iter().filter_map(|node| node.value.as_ref())
+    }
+
+    fn value(&self, index: usize) -> &T {
+        self.nodes[index].value.as_ref().unwrap()
+    }
+
+    fn alloc(&mut self, value: T) -> usize {
+        let index = self.free.pop().unwrap_or(self.nodes.len());
+        let node = Node {
+            value: Some(value),
+            left: None,
+            right: None,
+            up: index,
+        };
+        if index == self.nodes.len() {
+            self.nodes.push(node);
+        } else {
+            self.nodes[index] = node;
+        }
+        index
+    }
+
+    // Move the nodes of `heap` into the free slots of `self` or to its end, and return the root of `heap` in it.
+    // The free slots of `heap` are skipped, and they are at most three quarters of its slots.
+    fn move_from(&mut self, heap: &mut BottomUpSkewHeap<T>) -> Option<usize> {
+        let mut map = vec![usize::MAX; heap.nodes.len()];
+        for (old, node) in heap.nodes.iter().enumerate() {
+            if node.value.is_some() {
+                map[old] = self.free.pop().unwrap_or_else(|| {
+                    self.nodes.push(Node {
+                        value: None,
+                        left: None,
+                        right: None,
+                        up: 0,
+                    });
+                    self.nodes.len() - 1
+                });
+            }
+        }
+
+        for (old, node) in heap.nodes.drain(..).enumerate() {
+            if node.value.is_some() {
+                self.nodes[map[old]] = Node {
+                    value: node.value,
+                    left: node.left.map(|i| map[i]),
+                    right: node.right.map(|i| map[i]),
+                    up: map[node.up],
+                };
+            }
+        }
+        heap.free.clear();
+        heap.root.take().map(|root| map[root])
+    }
+
+    // Move the nodes into a new vector without free slots.
+    fn compact(&mut self) {
+        let mut heap = Self::new();
+        heap.root = heap.move_from(self);
+        heap.len = self.len;
+        *self = heap;
+    }
+
+    fn meld_nodes(&mut self, root1: Option<usize>, root2: Option<usize>) -> Option<usize> {
+        let (mut root1, mut root2) = match (root1, root2) {
+            (None, root2) => return root2,
+            (root1, None) => return root1,
+            (Some(r1), Some(r2)) => (r1, r2),
+        };
+
+        // Ensure root1 <= root2, so that the right path of root2 is exhausted first.
+        if self.value(root1) > self.value(root2) {
+            core::mem::swap(&mut root1, &mut root2);
+        }
+
+        // Setup loop variables
+        let mut node1 = self.nodes[root1].up;
+        let mut node2_opt = Some(self.nodes[root2].up);
+        let mut merged = None;
+
+        while let Some(node2) = node2_opt {
+            // Take the greater of the bottom nodes of the two right paths.
+            let node = if self.value(node1) > self.value(node2) {
+                let node = node1;
+                node1 = self.nodes[node].up;
+                node
+            } else {
+                node2_opt = (node2 != root2).then(|| self.nodes[node2].up);
+                node2
+            };
+
+            // Skew `node`: the old left child becomes the right child, and `merged` becomes the left child.
+            let left = self.nodes[node].left;
+            self.nodes[node].right = left;
+            self.nodes[node].left = merged;
+            self.nodes[node].up = match left {
+                Some(left) => {
+                    let bottom = self.nodes[left].up;
+                    self.nodes[left].up = node;
+                    bottom
+                }
+                None => node,
+            };
+            merged = Some(node);
+        }
+
+        // Make the merged nodes the right child of `node1`.
+        let merged = merged.unwrap();
+        let bottom = self.nodes[merged].up;
+        self.nodes[merged].up = node1;
+        self.nodes[node1].right = Some(merged);
+        self.nodes[root1].up = bottom;
+
+        Some(root1)
+    }
+}
+
+impl<T: Ord> Default for BottomUpSkewHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[rustfmt::skip]
+impl<T: Ord> MeldableHeap<T> for BottomUpSkewHeap<T> {
+    fn is_empty(&self) -> bool { self.is_empty() }
+    fn push(&mut self, value: T) { self.push(value) }
+    fn pop(&mut self) -> Option<T> { self.pop() }
+    fn peek(&self) -> Option<&T> { self.peek() }
+    fn meld(heap1: Self, heap2: Self) -> Self { Self::meld(heap1, heap2) }
+}
+
+struct Node<T> {
+    // `None` if the node is in the free list
+    value: Option<T>,
+    left: Option<usize>,
+    right: Option<usize>,
+    // The parent if the node is a right child, otherwise the bottom node of its right path
+    up: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::testing::{self, ModelConfig};
+    use crate::BottomUpSkewHeap;
+
+    #[test]
+    fn basic_test() {
+        testing::check_basic::<BottomUpSkewHeap<u32>>();
+    }
+
+    #[test]
+    fn large_drop_test() {
+        testing::check_deep::<BottomUpSkewHeap<u32>>(100000);
+    }
+
+    #[test]
+    fn compact_test() {
+        let mut heap1 = BottomUpSkewHeap::new();
+        for x in 0..10000 {
+            heap1.push(x);
+        }
+        for x in 0..9990 {
+            assert_eq!(heap1.pop(), Some(x));
+        }
+        assert!(heap1.nodes.len() <= 4 * heap1.len());
+
+        // `heap1` has fewer elements than `heap2`, so only its 10 nodes are moved.
+        let mut heap2 = BottomUpSkewHeap::new();
+        for x in 0..100 {
+            heap2.push(x);
+        }
+        let mut heap = BottomUpSkewHeap::meld(heap1, heap2);
+        assert_eq!(heap.len(), 110);
+        assert_eq!(heap.nodes.len(), 110);
+
+        let mut expected: Vec<_> = (0..100).chain(9990..10000).collect();
+        expected.sort();
+        let actual: Vec<_> = core::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn randomized_test() {
+        let config = ModelConfig {
+            seed: rand::random(),
+            ..ModelConfig::default()
+        };
+        testing::check_model::<BottomUpSkewHeap<u32>>(&config);
+    }
+}
@@ -0,0 +1,292 @@
+#![forbid(unsafe_code)]
+
+use alloc::vec::Vec;
+
+use crate::{MeldableHeap, PairingHeap};
+
+const MIN_BUCKETS: usize = 2;
+
+/// `CalendarQueue` is a priority queue of timestamped events implemented with calendar queue.
+/// `CalendarQueue` is a **min-heap**, which means that the earliest event is popped first.
+///
+/// The time axis is divided into days of equal width, and the days are assigned to the buckets cyclically
+/// like a calendar. `pop` scans the buckets from the current day, so that both `push` and `pop` take
+/// O(1) expected time if the events are spread evenly, as in discrete-event simulations.
+/// The number of buckets and the width of a day are adjusted as the number of events changes.
+///
+/// Each bucket is a [`PairingHeap`], so that the events in a bucket are ordered by `T`,
+/// and two buckets are melded in O(1) time when the calendar shrinks.
+///
+/// # Reference
+/// Brown, Randy (1988).
+/// ["Calendar Queues: A Fast O(1) Priority Queue Implementation for the Simulation Event Set Problem"][Brown-1988]
+///
+/// [Brown-1988]: https://doi.org/10.1145/63039.63045
+pub struct CalendarQueue<T: Timestamped> {
+    // The number of buckets is a power of two.
+    buckets: Vec<PairingHeap<T>>,
+    // The width of a day is `1 << shift`.
+    shift: u32,
+    // The current day. No events are earlier than this day.
+    day: u64,
+    len: usize,
+}
+
+impl<T: Timestamped> CalendarQueue<T> {
+    /// Constructs a empty `CalendarQueue`.
+    /// O(1) time.
+    pub fn new() -> Self {
+        Self {
+            buckets: (0..MIN_BUCKETS).map(|_| PairingHeap::new()).collect(),
+            shift: 0,
+            day: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns `true` if the queue contains no events.
+    /// O(1) time.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of events in the queue.
+    /// O(1) time.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Inserts an event into the queue.
+    /// O(1) expected amortized time.
+    pub fn push(&mut self, value: T) {
+        let day = value.timestamp() >> self.shift;
+        self.day = self.day.min(day);
+        let mask = self.buckets.len() - 1;
+        self.buckets[day as usize & mask].push(value);
+        self.len += 1;
+
+        if self.len > 2 * self.buckets.len() {
+            self.grow();
+        }
+    }
+
+    /// Removes the earliest event from the queue and returns it, or `None` if it is empty.
+    /// O(1) expected amortized time.
+    pub fn pop(&mut self) -> Option<T> {
+        self.day = self.find_day()?;
+        let mask = self.buckets.len() - 1;
+        let value = self.buckets[self.day as usize & mask].pop();
+        self.len -= 1;
+
+        if self.len < self.buckets.len() / 2 && self.buckets.len() > MIN_BUCKETS {
+            self.shrink();
+        }
+        value
+    }
+
+    /// Returns a reference to the earliest event in the queue, or `None` if it is empty.
+    /// O(1) expected time.
+    pub fn peek(&self) -> Option<&T> {
+        let day = self.find_day()?;
+        let mask = self.buckets.len() - 1;
+        self.buckets[day as usize & mask].peek()
+    }
+
+    /// Melds two queues into a single queue.
+    /// O(min(n, m)) expected amortized time, because the events of the smaller queue are pushed one by one.
+    pub fn meld(mut queue1: CalendarQueue<T>, mut queue2: CalendarQueue<T>) -> CalendarQueue<T> {
+        if queue1.len < queue2.len {
+            core::mem::swap(&mut queue1, &mut queue2);
+        }
+        let mut values = Vec::with_capacity(queue2.len);
+        for bucket in &mut queue2.buckets {
+            bucket.drain_with(|value| values.push(value));
+        }
+        for value in values {
+            queue1.push(value);
+        }
+        queue1
+    }
+
+    /// Returns an iterator that visits all events in the queue, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.buckets.iter().flat_map(|bucket| bucket.iter())
+    }
+
+    // Returns the day of the earliest event, or `None` if the queue is empty.
+    fn find_day(&self) -> Option<u64> {
+        if self.len == 0 {
+            return None;
+        }
+
+        // Scan the buckets for a year from the current day.
+        let mask = self.buckets.len() - 1;
+        for day in self.day..self.day.saturating_add(self.buckets.len() as u64) {
+            let Some(value) = self.buckets[day as usize & mask].peek() else {
+                continue;
+            };
+            if value.timestamp() >> self.shift <= day {
+                return Some(day);
+            }
+        }
+
+        // All events are more than a year ahead. Search the earliest one directly.
+        let earliest = self
+            .buckets
+            .iter()
+            .filter_map(|bucket| bucket.peek())
+            .min()?;
+        Some(earliest.timestamp() >> self.shift)
+    }
+
+    // Double the number of buckets, and adjust the width of a day to the density of the events.
+    fn grow(&mut self) {
+        let mut values = Vec::with_capacity(self.len);
+        for bucket in &mut self.buckets {
+            bucket.drain_with(|value| values.push(value));
+        }
+
+        // Use three times the average interval between the events as the width of a day.
+        let min = values.iter().map(|value| value.timestamp()).min().unwrap();
+        let max = values.iter().map(|value| value.timestamp()).max().unwrap();
+        let width = ((max - min) / values.len() as u64).saturating_mul(3);
+        self.shift = width.checked_ilog2().unwrap_or(0);
+        self.day = min >> self.shift;
+
+        let len = 2 * self.buckets.len();
+        self.buckets.resize_with(len, PairingHeap::new);
+        let mask = len - 1;
+        for value in values {
+            let day = value.timestamp() >> self.shift;
+            self.buckets[day as usize & mask].push(value);
+        }
+    }
+
+    // Halve the number of buckets by melding the buckets which share the same day of the year.
+    fn shrink(&mut self) {
+        let half = self.buckets.len() / 2;
+        for i in 0..half {
+            let upper = self.buckets.pop().unwrap();
+            let lower = core::mem::take(&mut self.buckets[half - 1 - i]);
+            self.buckets[half - 1 - i] = PairingHeap::meld(lower, upper);
+        }
+    }
+}
+
+impl<T: Timestamped> Default for CalendarQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[rustfmt::skip]
+impl<T: Timestamped> MeldableHeap<T> for CalendarQueue<T> {
+    fn is_empty(&self) -> bool { self.is_empty() }
+    fn push(&mut self, value: T) { self.push(value) }
+    fn pop(&mut self) -> Option<T> { self.pop() }
+    fn peek(&self) -> Option<&T> { self.peek() }
+    fn meld(heap1: Self, heap2: Self) -> Self { Self::meld(heap1, heap2) }
+}
+
+/// `Timestamped` gives the time of an event in a [`CalendarQueue`].
+///
+/// The order of the times must be consistent with the order of the events,
+/// i.e. `a <= b` implies `a.timestamp() <= b.timestamp()`.
+pub trait Timestamped: Ord {
+    /// Returns the time of the event.
+    fn timestamp(&self) -> u64;
+}
+
+macro_rules! impl_timestamped {
+    ($($t:ty),*) => {
+        $(
+            impl Timestamped for $t {
+                fn timestamp(&self) -> u64 {
+                    *self as u64
+                }
+            }
+        )*
+    };
+}
+
+impl_timestamped!(u8, u16, u32, u64, usize);
+
+// Tuples are ordered by the first element first, so that the first element can be the time.
+impl<A: Timestamped, B: Ord> Timestamped for (A, B) {
+    fn timestamp(&self) -> u64 {
+        self.0.timestamp()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BinaryHeap;
+    use core::cmp::Reverse;
+
+    use crate::testing::{self, ModelConfig};
+    use crate::CalendarQueue;
+
+    #[test]
+    fn basic_test() {
+        testing::check_basic::<CalendarQueue<u32>>();
+    }
+
+    #[test]
+    fn large_drop_test() {
+        testing::check_deep::<CalendarQueue<u32>>(100000);
+    }
+
+    #[test]
+    fn randomized_test() {
+        let config = ModelConfig {
+            seed: rand::random(),
+            ..ModelConfig::default()
+        };
+        testing::check_model::<CalendarQueue<u32>>(&config);
+    }
+
+    #[test]
+    fn resize_test() {
+        let n = 100000u64;
+        let mut queue = CalendarQueue::new();
+        for i in 0..n {
+            queue.push((i * 7919 % n) * 1000);
+        }
+        assert!(queue.buckets.len() >= n as usize / 2);
+        assert!(queue.shift > 0);
+
+        // Events far ahead of the others are found by the direct search.
+        queue.push(u64::MAX);
+        for i in 0..n {
+            assert_eq!(queue.pop(), Some(i * 1000));
+        }
+        assert!(queue.buckets.len() <= 4);
+        assert_eq!(queue.pop(), Some(u64::MAX));
+        assert_eq!(queue.pop(), None);
+    }
+
+    #[test]
+    fn simulation_test() {
+        // Each event schedules the next event after a random delay, and sometimes an event in the past.
+        let mut queue = CalendarQueue::new();
+        let mut expected = BinaryHeap::new();
+        for i in 0..1000u32 {
+            let t = rand::random::<u64>() % 1000;
+            queue.push((t, i));
+            expected.push(Reverse((t, i)));
+        }
+        for i in 1000..100000u32 {
+            let actual = queue.pop();
+            assert_eq!(actual, expected.pop().map(|r| r.0));
+            let (t, _) = actual.unwrap();
+            let next = if i % 100 == 0 {
+                t.saturating_sub(500)
+            } else {
+                t + rand::random::<u64>() % 2000
+            };
+            queue.push((next, i));
+            expected.push(Reverse((next, i)));
+        }
+        assert_eq!(queue.len(), expected.len());
+    }
+}
@@ -0,0 +1,473 @@
+#![forbid(unsafe_code)]
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{MeldableHeap, PairingStrategy, SiblingList, TwoPass};
+
+/// `DoubleEndedPairingHeap` is a double-ended priority queue implemented with twin pairing heaps.
+/// Both the minimum and the maximum elements can be popped.
+///
+/// Every element belongs to two pairing heaps at the same time: a min-heap and a max-heap.
+/// When an element is popped from one of them, it is also removed from the other by cutting its subtree.
+///
+/// Nodes are stored in a vector and linked by indices, so that a node can be shared by the two heaps.
+/// The nodes of [`PairingHeap`](crate::PairingHeap) are not reused: they are owned by their parents,
+/// so a node cannot belong to two trees, and it has no link to its parent to be cut out of the other tree.
+/// The two trees are linked by the same [`PairingStrategy`] machinery as `PairingHeap`, through [`SiblingList`].
+/// The vector is compacted when fewer than a quarter of its slots are in use.
+///
+/// # Reference
+/// Chong, Kyun-Rak; Sahni, Sartaj (2000).
+/// ["Correspondence-Based Data Structures for Double-Ended Priority Queues"][Chong-Sahni-2000]
+///
+/// [Chong-Sahni-2000]: https://doi.org/10.1145/351827.351829
+pub struct DoubleEndedPairingHeap<T: Ord> {
+    nodes: Vec<Node<T>>,
+    free: Vec<usize>,
+    min_root: Option<usize>,
+    max_root: Option<usize>,
+    len: usize,
+}
+
+impl<T: Ord> DoubleEndedPairingHeap<T> {
+    /// Constructs a empty `DoubleEndedPairingHeap`.
+    /// O(1) time.
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            min_root: None,
+            max_root: None,
+            len: 0,
+        }
+    }
+
+    /// Returns `true` if the heap contains no elements.
+    /// O(1) time.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of elements in the heap.
+    /// O(1) time.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Inserts a value into the heap.
+    /// O(1) time.
+    pub fn push(&mut self, value: T) {
+        let index = self.alloc(value);
+        self.min_root = Some(self.meld_trees(Side::Min, self.min_root, index));
+        self.max_root = Some(self.meld_trees(Side::Max, self.max_root, index));
+        self.len += 1;
+    }
+
+    /// Removes the minimum element from the heap and returns it, or `None` if it is empty.
+    /// O(log n) amortized time.
+    pub fn pop_min(&mut self) -> Option<T> {
+        let index = self.min_root?;
+        Some(self.remove(index))
+    }
+
+    /// Removes the maximum element from the heap and returns it, or `None` if it is empty.
+    /// O(log n) amortized time.
+    pub fn pop_max(&mut self) -> Option<T> {
+        let index = self.max_root?;
+        Some(self.remove(index))
+    }
+
+    /// Returns a reference to the minimum element in the heap, or `None` if it is empty.
+    /// O(1) time.
+    pub fn peek_min(&self) -> Option<&T> {
+        self.min_root.map(|index| self.value(index))
+    }
+
+    /// Returns a reference to the maximum element in the heap, or `None` if it is empty.
+    /// O(1) time.
+    pub fn peek_max(&self) -> Option<&T> {
+        self.max_root.map(|index| self.value(index))
+    }
+
+    /// Melds two heaps into a single heap.
+    /// O(min(n, m)) time to move the nodes of the smaller heap into the storage of the larger one,
+    /// where n and m are the numbers of elements, plus O(1) time to link the trees.
+    pub fn meld(
+        mut heap1: DoubleEndedPairingHeap<T>,
+        mut heap2: DoubleEndedPairingHeap<T>,
+    ) -> DoubleEndedPairingHeap<T> {
+        if heap1.len < heap2.len {
+            core::mem::swap(&mut heap1, &mut heap2);
+        }
+
+        let (min_root, max_root) = heap1.move_from(&mut heap2);
+        heap1.len += heap2.len;
+        if let Some(min_root) = min_root {
+            heap1.min_root = Some(heap1.meld_trees(Side::Min, heap1.min_root, min_root));
+        }
+        if let Some(max_root) = max_root {
+            heap1.max_root = Some(heap1.meld_trees(Side::Max, heap1.max_root, max_root));
+        }
+        heap1
+    }
+
+    /// Returns an iterator that visits all elements in the heap, in arbitrary order.
+    /// O(n) time, because the vector is compacted when most of its slots are free.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.nodes.iter().filter_map(|node| node.value.as_ref())
+    }
+
+    fn value(&self, index: usize) -> &T {
+        self.nodes[index].value.as_ref().unwrap()
+    }
+
+    fn alloc(&mut self, value: T) -> usize {
+        let node = Node {
+            value: Some(value),
+            links: [Links::default(); 2],
+        };
+        if let Some(index) = self.free.pop() {
+            self.nodes[index] = node;
+            index
+        } else {
+            self.nodes.push(node);
+            self.nodes.len() - 1
+        }
+    }
+
+    fn meld_trees(&mut self, side: Side, root: Option<usize>, tree: usize) -> usize {
+        match root {
+            Some(root) => Trees::new(&mut self.nodes, side).link(root, tree),
+            None => tree,
+        }
+    }
+
+    // Remove the node at `index` from both the min-heap and the max-heap.
+    fn remove(&mut self, index: usize) -> T {
+        self.min_root = self.remove_from(Side::Min, self.min_root.unwrap(), index);
+        self.max_root = self.remove_from(Side::Max, self.max_root.unwrap(), index);
+        self.len -= 1;
+        self.free.push(index);
+        let value = self.nodes[index].value.take().unwrap();
+        if self.nodes.len() > 4 * self.len {
+            self.compact();
+        }
+        value
+    }
+
+    // Move the nodes of `heap` into the free slots of `self` or to its end, and return the roots of `heap` in it.
+    // The free slots of `heap` are skipped, and they are at most three quarters of its slots.
+    fn move_from(
+        &mut self,
+        heap: &mut DoubleEndedPairingHeap<T>,
+    ) -> (Option<usize>, Option<usize>) {
+        let mut map = vec![usize::MAX; heap.nodes.len()];
+        for (old, node) in heap.nodes.iter().enumerate() {
+            if node.value.is_some() {
+                map[old] = self.free.pop().unwrap_or_else(|| {
+                    self.nodes.push(Node {
+                        value: None,
+                        links: [Links::default(); 2],
+                    });
+                    self.nodes.len() - 1
+                });
+            }
+        }
+
+        let shift = |index: Option<usize>| index.map(|i| map[i]);
+        let shift_links = |links: Links| Links {
+            child: shift(links.child),
+            next: shift(links.next),
+            prev: shift(links.prev),
+        };
+        for (old, node) in heap.nodes.drain(..).enumerate() {
+            if node.value.is_some() {
+                self.nodes[map[old]] = Node {
+                    value: node.value,
+                    links: node.links.map(shift_links),
+                };
+            }
+        }
+        heap.free.clear();
+        (shift(heap.min_root.take()), shift(heap.max_root.take()))
+    }
+
+    // Move the nodes into a new vector without free slots.
+    fn compact(&mut self) {
+        let mut heap = Self::new();
+        (heap.min_root, heap.max_root) = heap.move_from(self);
+        heap.len = self.len;
+        *self = heap;
+    }
+
+    // Remove the node at `index` from the tree of `root`, and return the new root.
+    fn remove_from(&mut self, side: Side, root: usize, index: usize) -> Option<usize> {
+        let mut trees = Trees::new(&mut self.nodes, side);
+        let links = trees.links(index);
+        let children = links
+            .child
+            .map(|first| TwoPass::link_all(&mut trees, first));
+        if let Some(children) = children {
+            trees.links_mut(children).prev = None;
+        }
+        if index == root {
+            return children;
+        }
+
+        // Cut the subtree of `index` out of the tree.
+        let prev = links.prev.unwrap();
+        if trees.links(prev).child == Some(index) {
+            trees.links_mut(prev).child = links.next;
+        } else {
+            trees.links_mut(prev).next = links.next;
+        }
+        if let Some(next) = links.next {
+            trees.links_mut(next).prev = Some(prev);
+        }
+
+        Some(match children {
+            Some(children) => trees.link(root, children),
+            None => root,
+        })
+    }
+}
+
+impl<T: Ord> Default for DoubleEndedPairingHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[rustfmt::skip]
+impl<T: Ord> MeldableHeap<T> for DoubleEndedPairingHeap<T> {
+    fn is_empty(&self) -> bool { self.is_empty() }
+    fn push(&mut self, value: T) { self.push(value) }
+    fn pop(&mut self) -> Option<T> { self.pop_min() }
+    fn peek(&self) -> Option<&T> { self.peek_min() }
+    fn meld(heap1: Self, heap2: Self) -> Self { Self::meld(heap1, heap2) }
+}
+
+#[derive(Clone, Copy)]
+enum Side {
+    Min = 0,
+    Max = 1,
+}
+
+struct Node<T> {
+    // `None` if the node is in the free list
+    value: Option<T>,
+    // The links in the min-heap and the max-heap, indexed by `Side`
+    links: [Links; 2],
+}
+
+#[derive(Clone, Copy, Default)]
+struct Links {
+    child: Option<usize>,
+    next: Option<usize>,
+    // The parent if the node is the first child, otherwise the previous sibling
+    prev: Option<usize>,
+}
+
+// `Trees` exposes the trees of one side to `PairingStrategy`.
+struct Trees<'a, T> {
+    nodes: &'a mut Vec<Node<T>>,
+    side: Side,
+}
+
+impl<'a, T: Ord> Trees<'a, T> {
+    fn new(nodes: &'a mut Vec<Node<T>>, side: Side) -> Self {
+        Self { nodes, side }
+    }
+
+    fn links(&self, index: usize) -> Links {
+        self.nodes[index].links[self.side as usize]
+    }
+
+    fn links_mut(&mut self, index: usize) -> &mut Links {
+        &mut self.nodes[index].links[self.side as usize]
+    }
+
+    // Returns `true` if `index1` should be the parent of `index2`.
+    fn precedes(&self, index1: usize, index2: usize) -> bool {
+        let value1 = self.nodes[index1].value.as_ref().unwrap();
+        let value2 = self.nodes[index2].value.as_ref().unwrap();
+        match self.side {
+            Side::Min => value1 <= value2,
+            Side::Max => value1 >= value2,
+        }
+    }
+}
+
+impl<T: Ord> SiblingList for Trees<'_, T> {
+    type Tree = usize;
+
+    fn take_next(&mut self, tree: &mut usize) -> Option<usize> {
+        self.links_mut(*tree).next.take()
+    }
+
+    fn set_next(&mut self, tree: &mut usize, next: Option<usize>) {
+        self.links_mut(*tree).next = next;
+    }
+
+    fn link(&mut self, tree1: usize, tree2: usize) -> usize {
+        let (parent, child) = if self.precedes(tree1, tree2) {
+            (tree1, tree2)
+        } else {
+            (tree2, tree1)
+        };
+
+        let first_child = self.links(parent).child;
+        if let Some(first_child) = first_child {
+            self.links_mut(first_child).prev = Some(child);
+        }
+        let links = self.links_mut(child);
+        links.next = first_child;
+        links.prev = Some(parent);
+        self.links_mut(parent).child = Some(child);
+        parent
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeMap;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use crate::testing::{self, ModelConfig};
+    use crate::DoubleEndedPairingHeap;
+
+    #[test]
+    fn basic_test() {
+        testing::check_basic::<DoubleEndedPairingHeap<u32>>();
+    }
+
+    #[test]
+    fn large_drop_test() {
+        testing::check_deep::<DoubleEndedPairingHeap<u32>>(100000);
+    }
+
+    #[test]
+    fn randomized_test() {
+        let config = ModelConfig {
+            seed: rand::random(),
+            ..ModelConfig::default()
+        };
+        testing::check_model::<DoubleEndedPairingHeap<u32>>(&config);
+    }
+
+    #[test]
+    fn pop_min_max_test() {
+        let mut heap = DoubleEndedPairingHeap::new();
+        for x in [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5, 8, 9, 7, 9] {
+            heap.push(x);
+        }
+        assert_eq!(heap.len(), 15);
+        let mut mins = Vec::new();
+        let mut maxs = Vec::new();
+        while !heap.is_empty() {
+            mins.extend(heap.pop_min());
+            maxs.extend(heap.pop_max());
+        }
+        assert_eq!(mins, vec![1, 1, 2, 3, 3, 4, 5, 5]);
+        assert_eq!(maxs, vec![9, 9, 9, 8, 7, 6, 5]);
+    }
+
+    #[test]
+    fn bounded_test() {
+        // Keep the 10 smallest elements by evicting the maximum.
+        let mut heap = DoubleEndedPairingHeap::new();
+        for i in 0..1000 {
+            heap.push((i * 7919) % 1000);
+            if heap.len() > 10 {
+                heap.pop_max();
+            }
+        }
+        assert_eq!(heap.peek_max(), Some(&9));
+        let actual: Vec<_> = core::iter::from_fn(|| heap.pop_min()).collect();
+        assert_eq!(actual, (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn compact_test() {
+        let mut heap1 = DoubleEndedPairingHeap::new();
+        for x in 0..10000 {
+            heap1.push(x);
+        }
+        for x in 0..4995 {
+            assert_eq!(heap1.pop_min(), Some(x));
+            assert_eq!(heap1.pop_max(), Some(9999 - x));
+        }
+        assert!(heap1.nodes.len() <= 4 * heap1.len());
+
+        // `heap1` has fewer elements than `heap2`, so only its 10 nodes are moved.
+        let mut heap2 = DoubleEndedPairingHeap::new();
+        for x in 0..100 {
+            heap2.push(x);
+        }
+        let mut heap = DoubleEndedPairingHeap::meld(heap1, heap2);
+        assert_eq!(heap.len(), 110);
+        assert_eq!(heap.nodes.len(), 110);
+        assert_eq!(heap.peek_max(), Some(&5004));
+
+        let mut expected: Vec<_> = (0..100).chain(4995..5005).collect();
+        expected.sort();
+        let actual: Vec<_> = core::iter::from_fn(|| heap.pop_min()).collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn randomized_min_max_test() {
+        for _ in 0..1000 {
+            let mut heap = DoubleEndedPairingHeap::new();
+            // A multiset of the expected elements
+            let mut expected = BTreeMap::new();
+            let remove = |expected: &mut BTreeMap<u32, usize>, x: u32| {
+                let count = expected.get_mut(&x).unwrap();
+                *count -= 1;
+                if *count == 0 {
+                    expected.remove(&x);
+                }
+            };
+            for _ in 0..100 {
+                match rand::random::<u32>() % 4 {
+                    0 => {
+                        let x = rand::random::<u32>() % 100;
+                        heap.push(x);
+                        *expected.entry(x).or_insert(0) += 1;
+                    }
+                    1 => {
+                        let actual_x = heap.pop_min();
+                        let expected_x = expected.first_key_value().map(|(&x, _)| x);
+                        assert_eq!(actual_x, expected_x);
+                        if let Some(x) = expected_x {
+                            remove(&mut expected, x);
+                        }
+                    }
+                    2 => {
+                        let actual_x = heap.pop_max();
+                        let expected_x = expected.last_key_value().map(|(&x, _)| x);
+                        assert_eq!(actual_x, expected_x);
+                        if let Some(x) = expected_x {
+                            remove(&mut expected, x);
+                        }
+                    }
+                    3 => {
+                        let mut other = DoubleEndedPairingHeap::new();
+                        for _ in 0..rand::random::<u32>() % 10 {
+                            let x = rand::random::<u32>() % 100;
+                            other.push(x);
+                            *expected.entry(x).or_insert(0) += 1;
+                        }
+                        heap = DoubleEndedPairingHeap::meld(heap, other);
+                    }
+                    _ => unreachable!(),
+                }
+                assert_eq!(expected.is_empty(), heap.is_empty());
+                assert_eq!(expected.first_key_value().map(|(x, _)| x), heap.peek_min());
+                assert_eq!(expected.last_key_value().map(|(x, _)| x), heap.peek_max());
+            }
+        }
+    }
+}
@@ -0,0 +1,311 @@
+#![forbid(unsafe_code)]
+
+use core::fmt::{self, Debug, Write};
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{Aggregate, LeftistHeapCursor, PairingHeapCursor, SkewHeapCursor};
+
+// The exporters below visit the nodes with an explicit stack, so that they do not overflow the call stack
+// on deep trees. Nodes deeper than `max_depth` (the root is at depth 0) are replaced with a marker.
+
+// `BinaryCursor` abstracts the cursors of the heaps whose nodes have left and right children.
+pub(crate) trait BinaryCursor: Copy {
+    type Value: Debug;
+
+    fn value(&self) -> &Self::Value;
+    fn left(&self) -> Option<Self>;
+    fn right(&self) -> Option<Self>;
+    fn rank(&self) -> Option<usize>;
+}
+
+impl<T: Ord + Debug, A: Aggregate<T>> BinaryCursor for LeftistHeapCursor<'_, T, A> {
+    type Value = T;
+
+    fn value(&self) -> &T {
+        LeftistHeapCursor::value(self)
+    }
+    fn left(&self) -> Option<Self> {
+        LeftistHeapCursor::left(self)
+    }
+    fn right(&self) -> Option<Self> {
+        LeftistHeapCursor::right(self)
+    }
+    fn rank(&self) -> Option<usize> {
+        Some(LeftistHeapCursor::rank(self))
+    }
+}
+
+impl<T: Ord + Debug, A: Aggregate<T>> BinaryCursor for SkewHeapCursor<'_, T, A> {
+    type Value = T;
+
+    fn value(&self) -> &T {
+        SkewHeapCursor::value(self)
+    }
+    fn left(&self) -> Option<Self> {
+        SkewHeapCursor::left(self)
+    }
+    fn right(&self) -> Option<Self> {
+        SkewHeapCursor::right(self)
+    }
+    fn rank(&self) -> Option<usize> {
+        None
+    }
+}
+
+pub(crate) fn binary_to_dot<C: BinaryCursor>(root: Option<C>, max_depth: Option<usize>) -> String {
+    let mut dot = Dot::new();
+    let mut stack = Vec::new();
+    if let Some(root) = root {
+        stack.push((root, dot.next_id(), 0));
+    }
+    while let Some((cursor, id, depth)) = stack.pop() {
+        dot.node(id, cursor.value(), cursor.rank());
+        let len = stack.len();
+        for (child, label) in [(cursor.left(), "L"), (cursor.right(), "R")] {
+            let Some(child) = child else { continue };
+            let child_id = dot.next_id();
+            if max_depth.is_some_and(|max_depth| depth >= max_depth) {
+                dot.truncated(child_id);
+            } else {
+                stack.push((child, child_id, depth + 1));
+            }
+            let _ = writeln!(dot.out, "  n{id} -> n{child_id} [label=\"{label}\"];");
+        }
+        // Visit the left child first.
+        stack[len..].reverse();
+    }
+    dot.finish()
+}
+
+pub(crate) fn binary_to_json<C: BinaryCursor>(root: Option<C>, max_depth: Option<usize>) -> String {
+    let mut json = Json::new();
+    json.out.push_str("{\"root\":");
+    let mut stack = alloc::vec![Step::Node(root, 0)];
+    while let Some(step) = stack.pop() {
+        let (cursor, depth) = match step {
+            Step::Text(text) => {
+                json.out.push_str(text);
+                continue;
+            }
+            Step::Node(None, _) => {
+                json.out.push_str("null");
+                continue;
+            }
+            Step::Node(Some(cursor), depth) => (cursor, depth),
+        };
+        if max_depth.is_some_and(|max_depth| depth > max_depth) {
+            json.out.push_str("{\"truncated\":true}");
+            continue;
+        }
+
+        json.out.push_str("{\"value\":");
+        json.value(cursor.value());
+        if let Some(rank) = cursor.rank() {
+            let _ = write!(json.out, ",\"rank\":{rank}");
+        }
+        // The steps are pushed in reverse order.
+        stack.push(Step::Text("}"));
+        stack.push(Step::Node(cursor.right(), depth + 1));
+        stack.push(Step::Text(",\"right\":"));
+        stack.push(Step::Node(cursor.left(), depth + 1));
+        stack.push(Step::Text(",\"left\":"));
+    }
+    json.out.push('}');
+    json.out
+}
+
+// Child edges are solid, and sibling edges are dashed.
+pub(crate) fn pairing_to_dot<T: Ord + Debug>(
+    root: Option<PairingHeapCursor<'_, T>>,
+    aux: Option<PairingHeapCursor<'_, T>>,
+    max_depth: Option<usize>,
+) -> String {
+    let mut dot = Dot::new();
+    let mut stack = Vec::new();
+    for cursor in [aux, root].into_iter().flatten() {
+        stack.push((cursor, dot.next_id(), 0));
+    }
+    while let Some((cursor, id, depth)) = stack.pop() {
+        dot.node(id, cursor.value(), None);
+        if let Some(sibling) = cursor.next_sibling() {
+            let sibling_id = dot.next_id();
+            stack.push((sibling, sibling_id, depth));
+            let _ = writeln!(dot.out, "  n{id} -> n{sibling_id} [style=dashed];");
+        }
+        if let Some(child) = cursor.first_child() {
+            let child_id = dot.next_id();
+            if max_depth.is_some_and(|max_depth| depth >= max_depth) {
+                dot.truncated(child_id);
+            } else {
+                stack.push((child, child_id, depth + 1));
+            }
+            let _ = writeln!(dot.out, "  n{id} -> n{child_id};");
+        }
+    }
+    dot.finish()
+}
+
+pub(crate) fn pairing_to_json<T: Ord + Debug>(
+    root: Option<PairingHeapCursor<'_, T>>,
+    aux: Option<PairingHeapCursor<'_, T>>,
+    max_depth: Option<usize>,
+) -> String {
+    let mut json = Json::new();
+    json.out.push_str("{\"root\":");
+    let mut stack = Vec::new();
+    stack.push(Step::Text("]}"));
+    push_siblings(&mut stack, aux, 0);
+    stack.push(Step::Text(",\"aux\":["));
+    stack.push(Step::Node(root, 0));
+    while let Some(step) = stack.pop() {
+        let (cursor, depth) = match step {
+            Step::Text(text) => {
+                json.out.push_str(text);
+                continue;
+            }
+            Step::Node(None, _) => {
+                json.out.push_str("null");
+                continue;
+            }
+            Step::Node(Some(cursor), depth) => (cursor, depth),
+        };
+
+        json.out.push_str("{\"value\":");
+        json.value(cursor.value());
+        json.out.push_str(",\"children\":[");
+        if cursor.first_child().is_some() && max_depth.is_some_and(|max_depth| depth >= max_depth) {
+            json.out.push_str("],\"truncated\":true}");
+            continue;
+        }
+        stack.push(Step::Text("]}"));
+        push_siblings(&mut stack, cursor.first_child(), depth + 1);
+    }
+    json.out
+}
+
+// Push the steps to write a list of siblings separated by commas, in reverse order.
+fn push_siblings<'a, T: Ord>(
+    stack: &mut Vec<Step<PairingHeapCursor<'a, T>>>,
+    first: Option<PairingHeapCursor<'a, T>>,
+    depth: usize,
+) {
+    let len = stack.len();
+    let mut it = first;
+    while let Some(cursor) = it {
+        if stack.len() > len {
+            stack.push(Step::Text(","));
+        }
+        stack.push(Step::Node(Some(cursor), depth));
+        it = cursor.next_sibling();
+    }
+    stack[len..].reverse();
+}
+
+enum Step<C> {
+    Text(&'static str),
+    Node(Option<C>, usize),
+}
+
+struct Dot {
+    out: String,
+    next_id: usize,
+}
+
+impl Dot {
+    fn new() -> Self {
+        Self {
+            out: String::from("digraph {\n"),
+            next_id: 0,
+        }
+    }
+
+    fn next_id(&mut self) -> usize {
+        self.next_id += 1;
+        self.next_id - 1
+    }
+
+    fn node(&mut self, id: usize, value: &impl Debug, rank: Option<usize>) {
+        let _ = write!(self.out, "  n{id} [label=\"");
+        let _ = write!(Escape(&mut self.out), "{value:?}");
+        if let Some(rank) = rank {
+            let _ = write!(self.out, "\\nrank {rank}");
+        }
+        self.out.push_str("\"];\n");
+    }
+
+    fn truncated(&mut self, id: usize) {
+        let _ = writeln!(self.out, "  n{id} [label=\"...\", shape=plaintext];");
+    }
+
+    fn finish(mut self) -> String {
+        self.out.push_str("}\n");
+        self.out
+    }
+}
+
+struct Json {
+    out: String,
+}
+
+impl Json {
+    fn new() -> Self {
+        Self { out: String::new() }
+    }
+
+    // Write the `Debug` representation of `value` as a JSON string.
+    fn value(&mut self, value: &impl Debug) {
+        self.out.push('"');
+        let _ = write!(Escape(&mut self.out), "{value:?}");
+        self.out.push('"');
+    }
+}
+
+// `Escape` escapes the characters which cannot appear in a string literal of DOT and JSON.
+struct Escape<'a>(&'a mut String);
+
+impl Write for Escape<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            match c {
+                '"' => self.0.push_str("\\\""),
+                '\\' => self.0.push_str("\\\\"),
+                '\n' => self.0.push_str("\\n"),
+                c if c.is_control() => write!(self.0, "\\u{:04x}", c as u32)?,
+                c => self.0.push(c),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{LeftistHeap, PairingHeap, SkewHeap};
+
+    #[test]
+    #[cfg_attr(feature = "debug-invariants", ignore)]
+    fn deep_tree_test() {
+        let n = 1000000;
+        let mut pairing = PairingHeap::new();
+        let mut leftist = LeftistHeap::new();
+        let mut skew = SkewHeap::new();
+        for x in 0..n {
+            pairing.push(x);
+            leftist.push(x);
+            skew.push(n - x);
+        }
+        // `pairing` has a root with n - 1 children, and `skew` is a path of length n.
+        assert_eq!(pairing.to_dot(None).matches(" -> ").count(), n - 1);
+        assert_eq!(skew.to_dot(None).matches(" -> ").count(), n - 1);
+        assert_eq!(leftist.to_json(None).matches("value").count(), n);
+        assert_eq!(skew.to_json(None).matches("value").count(), n);
+        pairing.pop();
+        assert_eq!(pairing.to_json(None).matches("value").count(), n - 1);
+
+        assert_eq!(skew.to_dot(Some(9)).matches(" -> ").count(), 10);
+        assert_eq!(skew.to_json(Some(9)).matches("value").count(), 10);
+        assert!(pairing.to_json(Some(0)).contains("truncated"));
+    }
+}
@@ -0,0 +1,550 @@
+#![forbid(unsafe_code)]
+
+use alloc::vec::Vec;
+
+use crate::{AddressableHeap, Handle, MeldableHeap};
+
+/// `FibonacciHeap` is a priority queue implemented with Fibonacci heap.
+/// `FibonacciHeap` is a **min-heap**, which means that the minimum element is popped first.
+///
+/// `decrease_key` takes O(1) time in the worst case, which suits hard-real-time updates.
+/// It cuts the node from its parent and marks the parent, as the original Fibonacci heap does,
+/// but it never performs cascading cuts itself: a marked parent that loses a second child is put into a queue,
+/// and the queued cuts are performed by the next `pop` or `remove` before the trees are consolidated.
+/// Each queued cut is paid by the `decrease_key` which queued it, so the amortized bounds are unchanged.
+///
+/// Nodes are stored in a vector and linked by indices, so that elements can be addressed by [`Handle`]s.
+///
+/// # Why not a thin heap
+/// A thin heap (Kaplan–Tarjan) is a Fibonacci heap variant whose `decrease_key` is O(1) only amortized:
+/// like the cascading cuts of a Fibonacci heap, its repair can walk up many ancestors.
+/// A worst-case bound needs that walk deferred in either structure. It is simplest on the marks of
+/// a Fibonacci heap, which keeps the same handle API and the same amortized bounds as a thin heap.
+///
+/// # Reference
+/// Fredman, Michael L.; Tarjan, Robert E. (1987).
+/// ["Fibonacci heaps and their uses in improved network optimization algorithms"][Fredman-Tarjan-1987]
+///
+/// [Fredman-Tarjan-1987]: https://doi.org/10.1145/28869.28874
+pub struct FibonacciHeap<T: Ord> {
+    nodes: Vec<Node<T>>,
+    free: Vec<usize>,
+    // The root with the minimum element. The roots form a circular list.
+    min: Option<usize>,
+    // The head of the queue of deferred cuts, linked by `queue_next`.
+    queue: Option<usize>,
+    len: usize,
+}
+
+impl<T: Ord> FibonacciHeap<T> {
+    /// Constructs a empty `FibonacciHeap`.
+    /// O(1) time.
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            min: None,
+            queue: None,
+            len: 0,
+        }
+    }
+
+    /// Returns `true` if the heap contains no elements.
+    /// O(1) time.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of elements in the heap.
+    /// O(1) time.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Inserts a value into the heap and returns a handle to it.
+    /// O(1) time.
+    pub fn push(&mut self, value: T) -> Handle {
+        let index = self.alloc(value);
+        self.add_root(index);
+        self.update_min(index);
+        self.len += 1;
+        Handle(index)
+    }
+
+    /// Removes the minimum element from the heap and returns it, or `None` if it is empty.
+    /// O(log n) amortized time.
+    pub fn pop(&mut self) -> Option<T> {
+        let min = self.min?;
+        Some(self.remove_root(min))
+    }
+
+    /// Returns a reference to the minimum element in the heap, or `None` if it is empty.
+    /// O(1) time.
+    pub fn peek(&self) -> Option<&T> {
+        self.min.map(|index| self.value(index))
+    }
+
+    /// Melds two heaps into a single heap.
+    /// O(m) time, where m is the number of nodes in `heap2`.
+    ///
+    /// The nodes of `heap2` are moved into the storage of `heap1`.
+    /// Handles to the elements of `heap1` remain valid, but handles to the elements of `heap2` are invalidated.
+    pub fn meld(mut heap1: Self, mut heap2: Self) -> Self {
+        // The queue of `heap2` is not moved, so that the moved nodes need not be distinguished from queued nodes.
+        heap2.cascade();
+
+        let offset = heap1.nodes.len();
+        let shift = |index: Option<usize>| index.map(|i| i + offset);
+
+        heap1.nodes.extend(heap2.nodes.into_iter().map(|node| Node {
+            value: node.value,
+            parent: shift(node.parent),
+            child: shift(node.child),
+            prev: node.prev + offset,
+            next: node.next + offset,
+            degree: node.degree,
+            mark: node.mark,
+            queue_next: None,
+        }));
+        heap1
+            .free
+            .extend(heap2.free.into_iter().map(|i| i + offset));
+        heap1.len += heap2.len;
+        if let Some(min2) = shift(heap2.min) {
+            heap1.add_root(min2);
+            heap1.update_min(min2);
+        }
+        heap1
+    }
+
+    /// Returns a reference to the element of `handle`, or `None` if it has been removed.
+    /// O(1) time.
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.nodes.get(handle.0)?.value.as_ref()
+    }
+
+    /// Replaces the element of `handle` with `value`.
+    /// O(1) worst-case time: it cuts at most one node, and defers the cascading cuts to `pop` and `remove`.
+    ///
+    /// # Panics
+    /// Panics if the element has been removed or `value` is greater than the current element.
+    pub fn decrease_key(&mut self, handle: Handle, value: T) {
+        let index = handle.0;
+        let current = self
+            .nodes
+            .get_mut(index)
+            .and_then(|node| node.value.as_mut())
+            .expect("decrease_key: invalid handle");
+        assert!(
+            value <= *current,
+            "decrease_key: new value is greater than the current value"
+        );
+        *current = value;
+
+        match self.nodes[index].parent {
+            Some(parent) if self.value(index) < self.value(parent) => self.cut(index),
+            Some(_) => return,
+            None => {}
+        }
+        self.update_min(index);
+    }
+
+    /// Removes the element of `handle` from the heap and returns it, or `None` if it has been removed.
+    /// O(log n) amortized time.
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        let index = handle.0;
+        self.nodes.get(index)?.value.as_ref()?;
+        if self.nodes[index].parent.is_some() {
+            self.cut(index);
+        }
+        Some(self.remove_root(index))
+    }
+
+    /// Returns an iterator that visits all elements in the heap, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.nodes.iter().filter_map(|node| node.value.as_ref())
+    }
+
+    fn value(&self, index: usize) -> &T {
+        self.nodes[index].value.as_ref().unwrap()
+    }
+
+    fn alloc(&mut self, value: T) -> usize {
+        let index = self.free.pop().unwrap_or(self.nodes.len());
+        let node = Node {
+            value: Some(value),
+            parent: None,
+            child: None,
+            prev: index,
+            next: index,
+            degree: 0,
+            mark: Mark::Unmarked,
+            queue_next: None,
+        };
+        if index < self.nodes.len() {
+            self.nodes[index] = node;
+        } else {
+            self.nodes.push(node);
+        }
+        index
+    }
+
+    fn update_min(&mut self, index: usize) {
+        match self.min {
+            Some(min) if self.value(min) <= self.value(index) => {}
+            _ => self.min = Some(index),
+        }
+    }
+
+    // Concatenate the circular lists containing `a` and `b`.
+    fn splice(&mut self, a: usize, b: usize) {
+        let a_next = self.nodes[a].next;
+        let b_prev = self.nodes[b].prev;
+        self.nodes[a].next = b;
+        self.nodes[b].prev = a;
+        self.nodes[b_prev].next = a_next;
+        self.nodes[a_next].prev = b_prev;
+    }
+
+    // Remove `index` from its circular list, and make it a list of its own.
+    fn unlink(&mut self, index: usize) {
+        let Node { prev, next, .. } = self.nodes[index];
+        self.nodes[prev].next = next;
+        self.nodes[next].prev = prev;
+        self.nodes[index].prev = index;
+        self.nodes[index].next = index;
+    }
+
+    // Add the list of `index` to the roots. The caller is responsible for updating `min`.
+    fn add_root(&mut self, index: usize) {
+        if let Some(min) = self.min {
+            self.splice(min, index);
+        }
+    }
+
+    // Detach the subtree rooted at `index` from its parent, and make it a root.
+    // The caller is responsible for updating `min`.
+    fn cut(&mut self, index: usize) {
+        let parent = self.nodes[index].parent.take().unwrap();
+        if self.nodes[parent].child == Some(index) {
+            let next = self.nodes[index].next;
+            self.nodes[parent].child = (next != index).then_some(next);
+        }
+        self.unlink(index);
+        self.nodes[parent].degree -= 1;
+        self.nodes[index].mark = Mark::Unmarked;
+        self.add_root(index);
+        self.lose_child(parent);
+    }
+
+    // Mark `index` when it loses its first child, and queue its cut when it loses the second one.
+    // O(1) time. The queued cut is performed by `cascade`.
+    fn lose_child(&mut self, index: usize) {
+        // Roots are never marked.
+        if self.nodes[index].parent.is_none() {
+            return;
+        }
+        match self.nodes[index].mark {
+            Mark::Unmarked => self.nodes[index].mark = Mark::Marked,
+            Mark::Marked => {
+                self.nodes[index].mark = Mark::Queued;
+                self.nodes[index].queue_next = self.queue.replace(index);
+            }
+            Mark::Queued => {}
+        }
+    }
+
+    // Perform the queued cuts. A cut may queue the parent of the cut node, which is cut in turn.
+    // Afterwards, every node has lost at most one child since it became a child, as in the original Fibonacci heap.
+    fn cascade(&mut self) {
+        while let Some(index) = self.queue {
+            self.queue = self.nodes[index].queue_next.take();
+            // The node may have been cut by `decrease_key` since it was queued.
+            if self.nodes[index].mark == Mark::Queued {
+                self.cut(index);
+            }
+        }
+    }
+
+    // Link two roots of the same degree and return the root of the new tree.
+    // Both roots must be detached from the root list.
+    fn link(&mut self, mut x: usize, mut y: usize) -> usize {
+        // Ensure x <= y
+        if self.value(x) > self.value(y) {
+            core::mem::swap(&mut x, &mut y);
+        }
+
+        // Make `y` a child of `x`.
+        match self.nodes[x].child {
+            Some(child) => self.splice(child, y),
+            None => self.nodes[x].child = Some(y),
+        }
+        self.nodes[y].parent = Some(x);
+        self.nodes[x].degree += 1;
+        x
+    }
+
+    // Remove the root `index` from the heap, and link the remaining roots until their degrees are distinct.
+    fn remove_root(&mut self, index: usize) -> T {
+        self.cascade();
+
+        // The children become roots.
+        if let Some(child) = self.nodes[index].child.take() {
+            let mut it = child;
+            loop {
+                self.nodes[it].parent = None;
+                self.nodes[it].mark = Mark::Unmarked;
+                it = self.nodes[it].next;
+                if it == child {
+                    break;
+                }
+            }
+            self.splice(index, child);
+        }
+
+        let mut roots = Vec::new();
+        let mut it = self.nodes[index].next;
+        while it != index {
+            roots.push(it);
+            it = self.nodes[it].next;
+        }
+        self.unlink(index);
+
+        let mut buckets: Vec<Option<usize>> = Vec::new();
+        for mut root in roots {
+            self.unlink(root);
+            loop {
+                let degree = self.nodes[root].degree as usize;
+                if buckets.len() <= degree {
+                    buckets.resize(degree + 1, None);
+                }
+                match buckets[degree].take() {
+                    Some(other) => root = self.link(root, other),
+                    None => {
+                        buckets[degree] = Some(root);
+                        break;
+                    }
+                }
+            }
+        }
+
+        self.min = None;
+        for root in buckets.into_iter().flatten() {
+            self.add_root(root);
+            self.update_min(root);
+        }
+
+        self.len -= 1;
+        self.free.push(index);
+        self.nodes[index].value.take().unwrap()
+    }
+}
+
+impl<T: Ord> Default for FibonacciHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[rustfmt::skip]
+impl<T: Ord> MeldableHeap<T> for FibonacciHeap<T> {
+    fn is_empty(&self) -> bool { self.is_empty() }
+    fn push(&mut self, value: T) { self.push(value); }
+    fn pop(&mut self) -> Option<T> { self.pop() }
+    fn peek(&self) -> Option<&T> { self.peek() }
+    fn meld(heap1: Self, heap2: Self) -> Self { Self::meld(heap1, heap2) }
+}
+
+#[rustfmt::skip]
+impl<T: Ord> AddressableHeap<T> for FibonacciHeap<T> {
+    fn push_with_handle(&mut self, value: T) -> Handle { self.push(value) }
+    fn get(&self, handle: Handle) -> Option<&T> { self.get(handle) }
+    fn decrease_key(&mut self, handle: Handle, value: T) { self.decrease_key(handle, value) }
+    fn remove(&mut self, handle: Handle) -> Option<T> { self.remove(handle) }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Mark {
+    Unmarked,
+    // The node has lost a child since it became a child.
+    Marked,
+    // The node has lost two children, and its cut is in the queue.
+    Queued,
+}
+
+struct Node<T> {
+    // `None` if the node is in the free list
+    value: Option<T>,
+    parent: Option<usize>,
+    // Any one of the children, which form a circular list.
+    child: Option<usize>,
+    prev: usize,
+    next: usize,
+    degree: u8,
+    mark: Mark,
+    queue_next: Option<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use super::Mark;
+    use crate::testing::{self, ModelConfig};
+    use crate::FibonacciHeap;
+
+    // Check the heap order, the links and the degrees, and return the number of roots.
+    fn check<T: Ord>(heap: &FibonacciHeap<T>) -> usize {
+        let Some(min) = heap.min else {
+            assert_eq!(heap.len, 0);
+            return 0;
+        };
+        let siblings = |first: usize| {
+            let mut list = vec![first];
+            let mut it = heap.nodes[first].next;
+            while it != first {
+                assert_eq!(heap.nodes[heap.nodes[it].prev].next, it);
+                list.push(it);
+                it = heap.nodes[it].next;
+            }
+            list
+        };
+
+        let roots = siblings(min);
+        let mut stack = roots.clone();
+        for &root in &roots {
+            assert!(heap.nodes[root].parent.is_none());
+            assert_eq!(heap.nodes[root].mark, Mark::Unmarked);
+            assert!(heap.value(min) <= heap.value(root));
+        }
+        let mut len = 0;
+        while let Some(node) = stack.pop() {
+            len += 1;
+            let children = heap.nodes[node].child.map_or(vec![], siblings);
+            assert_eq!(heap.nodes[node].degree as usize, children.len());
+            for &child in &children {
+                assert_eq!(heap.nodes[child].parent, Some(node));
+                assert!(heap.value(node) <= heap.value(child));
+            }
+            stack.extend(children);
+        }
+        assert_eq!(heap.len, len);
+        roots.len()
+    }
+
+    #[test]
+    fn basic_test() {
+        testing::check_basic::<FibonacciHeap<u32>>();
+    }
+
+    #[test]
+    fn large_drop_test() {
+        testing::check_deep::<FibonacciHeap<u32>>(100000);
+    }
+
+    #[test]
+    fn randomized_test() {
+        let config = ModelConfig {
+            seed: rand::random(),
+            ..ModelConfig::default()
+        };
+        testing::check_model::<FibonacciHeap<u32>>(&config);
+        testing::check_addressable::<FibonacciHeap<u32>>(&config);
+    }
+
+    #[test]
+    fn meld_test() {
+        let mut heap1 = FibonacciHeap::new();
+        let mut heap2 = FibonacciHeap::new();
+        let h1 = heap1.push(10);
+        heap1.push(30);
+        heap2.push(20);
+        heap2.push(5);
+        let mut heap = FibonacciHeap::meld(heap1, heap2);
+        heap.decrease_key(h1, 1);
+        assert_eq!(heap.len(), 4);
+        check(&heap);
+        let actual: Vec<_> = core::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(vec![1, 5, 20, 30], actual);
+    }
+
+    #[test]
+    fn deferred_cut_test() {
+        // Build a single binomial tree of degree 10, and cut all nodes from the deepest ones.
+        // The parents lose all their children one by one, so the original Fibonacci heap would cascade the cuts.
+        let n = 1 << 10;
+        let mut heap = FibonacciHeap::new();
+        let handles: Vec<_> = (0..=n).map(|i| heap.push(n + i)).collect();
+        heap.pop();
+        assert_eq!(check(&heap), 1);
+
+        let depth = |heap: &FibonacciHeap<i64>, i: usize| {
+            core::iter::successors(Some(i), |&j| heap.nodes[j].parent).count()
+        };
+        let mut order: Vec<_> = (1..=n as usize).collect();
+        order.sort_by_key(|&i| core::cmp::Reverse(depth(&heap, i)));
+
+        let mut max_queued = 0;
+        for (k, &i) in order.iter().enumerate() {
+            let roots = check(&heap);
+            heap.decrease_key(handles[i], -(k as i64));
+            // `decrease_key` adds at most the node itself to the roots.
+            assert!(check(&heap) <= roots + 1);
+            let queued = heap.nodes.iter().filter(|node| node.mark == Mark::Queued);
+            max_queued = max_queued.max(queued.count());
+        }
+        assert!(max_queued >= n as usize / 16, "{max_queued}");
+
+        // `pop` performs the deferred cuts before consolidating the trees.
+        let mut expected: Vec<_> = (0..n).map(|k| -k).collect();
+        expected.sort();
+        let mut actual = Vec::new();
+        while let Some(x) = heap.pop() {
+            assert!(heap.queue.is_none());
+            check(&heap);
+            actual.push(x);
+        }
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn long_decrease_key_sequence_test() {
+        // Decrease every key many times in orders that mark many nodes, with pops in between.
+        let n = 1 << 8;
+        let mut heap = FibonacciHeap::new();
+        let mut values: Vec<i64> = (0..=n).map(|i| 100 * n + i).collect();
+        let handles: Vec<_> = values.iter().map(|&x| heap.push(x)).collect();
+        heap.pop();
+        values[0] = i64::MAX;
+
+        for round in 0..20 {
+            let stride = [1, 3, 7, 64, 255][round % 5];
+            for k in 0..n {
+                let i = (1 + (k * stride + round as i64) % n) as usize;
+                if values[i] == i64::MAX {
+                    continue;
+                }
+                values[i] -= 1 + (i as i64 + round as i64) % 50;
+                let roots = check(&heap);
+                heap.decrease_key(handles[i], values[i]);
+                assert!(check(&heap) <= roots + 1);
+            }
+            assert_eq!(heap.peek(), values.iter().min());
+
+            // Pop a few elements to consolidate the trees.
+            for _ in 0..n / 32 {
+                let x = heap.pop().unwrap();
+                let i = (1..values.len())
+                    .find(|&i| values[i] == x && heap.get(handles[i]).is_none())
+                    .unwrap();
+                values[i] = i64::MAX;
+            }
+        }
+
+        let mut expected: Vec<_> = values.into_iter().filter(|&v| v != i64::MAX).collect();
+        expected.sort();
+        let actual: Vec<_> = core::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,42 @@
+#![forbid(unsafe_code)]
+
+/// `MeldableHeap` is the common interface of the heaps in this crate.
+/// All heaps are **min-heaps**, which means that the minimum element is popped first.
+pub trait MeldableHeap<T>: Default {
+    /// Returns `true` if the heap contains no elements.
+    fn is_empty(&self) -> bool;
+
+    /// Inserts a value into the heap.
+    fn push(&mut self, value: T);
+
+    /// Removes the minimum element from the heap and returns it, or `None` if it is empty.
+    fn pop(&mut self) -> Option<T>;
+
+    /// Returns a reference to the minimum element in the heap, or `None` if it is empty.
+    fn peek(&self) -> Option<&T>;
+
+    /// Melds two heaps into a single heap.
+    fn meld(heap1: Self, heap2: Self) -> Self;
+}
+
+/// `AddressableHeap` is a heap whose elements can be accessed through handles.
+pub trait AddressableHeap<T>: MeldableHeap<T> {
+    /// Inserts a value into the heap and returns a handle to it.
+    fn push_with_handle(&mut self, value: T) -> Handle;
+
+    /// Returns a reference to the element of `handle`, or `None` if it has been removed.
+    fn get(&self, handle: Handle) -> Option<&T>;
+
+    /// Replaces the element of `handle` with `value`, which must not be greater than the current one.
+    fn decrease_key(&mut self, handle: Handle, value: T);
+
+    /// Removes the element of `handle` from the heap and returns it, or `None` if it has been removed.
+    fn remove(&mut self, handle: Handle) -> Option<T>;
+}
+
+/// `Handle` refers to an element in an [`AddressableHeap`].
+///
+/// A handle is valid until its element is popped or removed from the heap.
+/// After that, the handle may refer to another element which is pushed later.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Handle(pub(crate) usize);
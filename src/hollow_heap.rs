@@ -0,0 +1,375 @@
+#![forbid(unsafe_code)]
+
+use alloc::vec::Vec;
+
+use crate::{AddressableHeap, Handle, MeldableHeap};
+
+/// `HollowHeap` is a priority queue implemented with hollow heap.
+/// `HollowHeap` is a **min-heap**, which means that the minimum element is popped first.
+///
+/// `decrease_key` and `remove` do not restructure the heap immediately.
+/// Instead, they leave a *hollow* node behind, which is discarded when it is visited by `pop`.
+///
+/// Nodes form a DAG, because a hollow node may have two parents.
+/// They are stored in a vector and linked by indices, so that the heap is written in safe Rust
+/// and dropping it never recurses.
+///
+/// # Reference
+/// Hansen, Thomas Dueholm; Kaplan, Haim; Tarjan, Robert E.; Zwick, Uri (2017).
+/// ["Hollow Heaps"][Hansen-Kaplan-Tarjan-Zwick-2017]
+///
+/// [Hansen-Kaplan-Tarjan-Zwick-2017]: https://arxiv.org/abs/1510.06535
+pub struct HollowHeap<T: Ord> {
+    nodes: Vec<Node<T>>,
+    free_nodes: Vec<usize>,
+    // `items[handle]` is the index of the node which holds the element of `handle`.
+    items: Vec<Option<usize>>,
+    free_items: Vec<usize>,
+    root: Option<usize>,
+    len: usize,
+}
+
+impl<T: Ord> HollowHeap<T> {
+    /// Constructs a empty `HollowHeap`.
+    /// O(1) time.
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            free_nodes: Vec::new(),
+            items: Vec::new(),
+            free_items: Vec::new(),
+            root: None,
+            len: 0,
+        }
+    }
+
+    /// Returns `true` if the heap contains no elements.
+    /// O(1) time.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of elements in the heap.
+    /// O(1) time.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Inserts a value into the heap and returns a handle to it.
+    /// O(1) time.
+    pub fn push(&mut self, value: T) -> Handle {
+        let item = match self.free_items.pop() {
+            Some(item) => item,
+            None => {
+                self.items.push(None);
+                self.items.len() - 1
+            }
+        };
+        let node = self.alloc(value, item);
+        self.items[item] = Some(node);
+        self.root = Some(self.meld_nodes(self.root, node));
+        self.len += 1;
+        Handle(item)
+    }
+
+    /// Removes the minimum element from the heap and returns it, or `None` if it is empty.
+    /// O(log n) amortized time.
+    pub fn pop(&mut self) -> Option<T> {
+        let root = self.root?;
+        let item = self.nodes[root].item;
+        self.remove(Handle(item))
+    }
+
+    /// Returns a reference to the minimum element in the heap, or `None` if it is empty.
+    /// O(1) time.
+    pub fn peek(&self) -> Option<&T> {
+        self.root.map(|root| self.value(root))
+    }
+
+    /// Melds two heaps into a single heap.
+    /// O(m) time, where m is the number of nodes in `heap2`.
+    ///
+    /// The nodes of `heap2` are moved into the storage of `heap1`.
+    /// Handles to the elements of `heap1` remain valid, but handles to the elements of `heap2` are invalidated.
+    pub fn meld(mut heap1: Self, heap2: Self) -> Self {
+        let node_offset = heap1.nodes.len();
+        let item_offset = heap1.items.len();
+        let shift = |index: Option<usize>| index.map(|i| i + node_offset);
+
+        heap1.nodes.extend(heap2.nodes.into_iter().map(|node| Node {
+            value: node.value,
+            item: node.item + item_offset,
+            rank: node.rank,
+            child: shift(node.child),
+            next: shift(node.next),
+            second_parent: shift(node.second_parent),
+        }));
+        heap1
+            .free_nodes
+            .extend(heap2.free_nodes.into_iter().map(|i| i + node_offset));
+        heap1.items.extend(heap2.items.into_iter().map(shift));
+        heap1
+            .free_items
+            .extend(heap2.free_items.into_iter().map(|i| i + item_offset));
+        heap1.len += heap2.len;
+        if let Some(root2) = shift(heap2.root) {
+            heap1.root = Some(heap1.meld_nodes(heap1.root, root2));
+        }
+        heap1
+    }
+
+    /// Returns a reference to the element of `handle`, or `None` if it has been removed.
+    /// O(1) time.
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        let node = (*self.items.get(handle.0)?)?;
+        Some(self.value(node))
+    }
+
+    /// Replaces the element of `handle` with `value`.
+    /// O(1) time.
+    ///
+    /// # Panics
+    /// Panics if the element has been removed or `value` is greater than the current element.
+    pub fn decrease_key(&mut self, handle: Handle, value: T) {
+        let u = self
+            .items
+            .get(handle.0)
+            .copied()
+            .flatten()
+            .expect("decrease_key: invalid handle");
+        assert!(
+            value <= *self.value(u),
+            "decrease_key: new value is greater than the current value"
+        );
+
+        if Some(u) == self.root {
+            self.nodes[u].value = Some(value);
+            return;
+        }
+
+        // Leave `u` as a hollow node, and move the element to a new node `v`.
+        self.nodes[u].value = None;
+        let v = self.alloc(value, handle.0);
+        self.items[handle.0] = Some(v);
+        self.nodes[v].rank = self.nodes[u].rank.saturating_sub(2);
+        self.nodes[v].child = Some(u);
+        self.nodes[u].second_parent = Some(v);
+        self.root = Some(self.meld_nodes(self.root, v));
+    }
+
+    /// Removes the element of `handle` from the heap and returns it, or `None` if it has been removed.
+    /// O(1) time if the element is not the minimum, O(log n) amortized time otherwise.
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        let node = self.items.get_mut(handle.0)?.take()?;
+        self.free_items.push(handle.0);
+        self.len -= 1;
+        let value = self.nodes[node].value.take();
+        if Some(node) == self.root {
+            self.delete_root();
+        }
+        value
+    }
+
+    /// Returns an iterator that visits all elements in the heap, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.nodes.iter().filter_map(|node| node.value.as_ref())
+    }
+
+    fn value(&self, index: usize) -> &T {
+        self.nodes[index].value.as_ref().unwrap()
+    }
+
+    fn alloc(&mut self, value: T, item: usize) -> usize {
+        let node = Node {
+            value: Some(value),
+            item,
+            rank: 0,
+            child: None,
+            next: None,
+            second_parent: None,
+        };
+        if let Some(index) = self.free_nodes.pop() {
+            self.nodes[index] = node;
+            index
+        } else {
+            self.nodes.push(node);
+            self.nodes.len() - 1
+        }
+    }
+
+    fn meld_nodes(&mut self, root: Option<usize>, node: usize) -> usize {
+        match root {
+            Some(root) => self.link(root, node),
+            None => node,
+        }
+    }
+
+    // Make the greater of two full nodes a child of the other, and return the parent.
+    fn link(&mut self, mut v: usize, mut w: usize) -> usize {
+        // Ensure v <= w
+        if self.value(v) > self.value(w) {
+            core::mem::swap(&mut v, &mut w);
+        }
+        self.nodes[w].next = self.nodes[v].child;
+        self.nodes[v].child = Some(w);
+        v
+    }
+
+    // Destroy the hollow root and its hollow descendants which have no other parent,
+    // then combine the remaining full nodes by ranked links.
+    fn delete_root(&mut self) {
+        let mut buckets: Vec<Option<usize>> = Vec::new();
+
+        // `h` is a list of hollow nodes to be destroyed, linked by `next`.
+        let mut h = self.root.take();
+        self.nodes[h.unwrap()].next = None;
+
+        while let Some(v) = h {
+            let mut w = self.nodes[v].child;
+            h = self.nodes[v].next;
+
+            while let Some(u) = w {
+                w = self.nodes[u].next;
+                if self.nodes[u].value.is_none() {
+                    match self.nodes[u].second_parent.take() {
+                        None => {
+                            // `u` has lost its only parent; destroy it later.
+                            self.nodes[u].next = h;
+                            h = Some(u);
+                        }
+                        Some(second_parent) => {
+                            // `u` is the last child of its second parent.
+                            if second_parent == v {
+                                w = None;
+                            } else {
+                                self.nodes[u].next = None;
+                            }
+                        }
+                    }
+                } else {
+                    let mut u = u;
+                    loop {
+                        let rank = self.nodes[u].rank as usize;
+                        if buckets.len() <= rank {
+                            buckets.resize(rank + 1, None);
+                        }
+                        let Some(other) = buckets[rank].take() else {
+                            buckets[rank] = Some(u);
+                            break;
+                        };
+                        u = self.link(u, other);
+                        self.nodes[u].rank += 1;
+                    }
+                }
+            }
+
+            self.free_nodes.push(v);
+        }
+
+        for node in buckets.into_iter().flatten() {
+            self.root = Some(self.meld_nodes(self.root, node));
+        }
+    }
+}
+
+impl<T: Ord> Default for HollowHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[rustfmt::skip]
+impl<T: Ord> MeldableHeap<T> for HollowHeap<T> {
+    fn is_empty(&self) -> bool { self.is_empty() }
+    fn push(&mut self, value: T) { self.push(value); }
+    fn pop(&mut self) -> Option<T> { self.pop() }
+    fn peek(&self) -> Option<&T> { self.peek() }
+    fn meld(heap1: Self, heap2: Self) -> Self { Self::meld(heap1, heap2) }
+}
+
+#[rustfmt::skip]
+impl<T: Ord> AddressableHeap<T> for HollowHeap<T> {
+    fn push_with_handle(&mut self, value: T) -> Handle { self.push(value) }
+    fn get(&self, handle: Handle) -> Option<&T> { self.get(handle) }
+    fn decrease_key(&mut self, handle: Handle, value: T) { self.decrease_key(handle, value) }
+    fn remove(&mut self, handle: Handle) -> Option<T> { self.remove(handle) }
+}
+
+struct Node<T> {
+    // `None` if the node is hollow
+    value: Option<T>,
+    item: usize,
+    rank: u8,
+    child: Option<usize>,
+    next: Option<usize>,
+    second_parent: Option<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use crate::testing::{self, ModelConfig};
+    use crate::HollowHeap;
+
+    #[test]
+    fn basic_test() {
+        testing::check_basic::<HollowHeap<u32>>();
+    }
+
+    #[test]
+    fn large_drop_test() {
+        testing::check_deep::<HollowHeap<u32>>(100000);
+    }
+
+    #[test]
+    fn randomized_test() {
+        let config = ModelConfig {
+            seed: rand::random(),
+            ..ModelConfig::default()
+        };
+        testing::check_model::<HollowHeap<u32>>(&config);
+        testing::check_addressable::<HollowHeap<u32>>(&config);
+    }
+
+    #[test]
+    fn large_decrease_key_drop_test() {
+        // `decrease_key` leaves a hollow node behind for each element.
+        let n = 1000000;
+        let mut heap = HollowHeap::new();
+        let handles: Vec<_> = (0..n).map(|i| heap.push(n + i)).collect();
+        heap.pop();
+        for (i, &h) in handles.iter().enumerate().skip(1) {
+            heap.decrease_key(h, n - i as i32);
+        }
+        drop(heap);
+    }
+
+    #[test]
+    fn meld_test() {
+        let mut heap1 = HollowHeap::new();
+        let mut heap2 = HollowHeap::new();
+        let h1 = heap1.push(10);
+        heap1.push(30);
+        heap2.push(20);
+        heap2.push(5);
+        let mut heap = HollowHeap::meld(heap1, heap2);
+        heap.decrease_key(h1, 1);
+        assert_eq!(heap.len(), 4);
+        let actual: Vec<_> = core::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(vec![1, 5, 20, 30], actual);
+    }
+
+    #[test]
+    #[should_panic(expected = "decrease_key: invalid handle")]
+    fn invalid_handle_test() {
+        let mut heap1 = HollowHeap::new();
+        let mut heap2 = HollowHeap::new();
+        heap1.push(1);
+        heap2.push(2);
+        // A handle of `heap2` which is out of range in `heap1`
+        let handle = heap2.push(3);
+        heap1.decrease_key(handle, 0);
+    }
+}
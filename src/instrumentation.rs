@@ -0,0 +1,184 @@
+#![forbid(unsafe_code)]
+
+/// `OpCounters` is the numbers of elementary operations performed by a heap,
+/// returned by `counters` if the `instrumentation` feature is enabled.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct OpCounters {
+    /// The number of comparisons between elements.
+    pub comparisons: u64,
+    /// The number of melds of two trees.
+    pub melds: u64,
+    /// The total number of nodes whose children are changed by the melds, i.e. the lengths of the meld paths.
+    pub meld_path: u64,
+    /// The number of passes over sibling lists in `PairingHeap`.
+    pub sibling_passes: u64,
+    /// The number of node allocations.
+    pub allocations: u64,
+}
+
+#[cfg(feature = "instrumentation")]
+impl OpCounters {
+    fn add(&mut self, other: &OpCounters) {
+        self.comparisons += other.comparisons;
+        self.melds += other.melds;
+        self.meld_path += other.meld_path;
+        self.sibling_passes += other.sibling_passes;
+        self.allocations += other.allocations;
+    }
+}
+
+// `Counters` is embedded in each instrumented heap.
+// It is a zero-sized type and all methods are no-ops if the `instrumentation` feature is disabled.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct Counters {
+    #[cfg(feature = "instrumentation")]
+    counts: OpCounters,
+}
+
+#[allow(unused_variables)]
+impl Counters {
+    #[inline(always)]
+    pub(crate) fn comparison(&mut self) {
+        #[cfg(feature = "instrumentation")]
+        {
+            self.counts.comparisons += 1;
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn meld(&mut self) {
+        #[cfg(feature = "instrumentation")]
+        {
+            self.counts.melds += 1;
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn meld_path(&mut self, nodes: u64) {
+        #[cfg(feature = "instrumentation")]
+        {
+            self.counts.meld_path += nodes;
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn sibling_pass(&mut self) {
+        #[cfg(feature = "instrumentation")]
+        {
+            self.counts.sibling_passes += 1;
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn allocation(&mut self) {
+        #[cfg(feature = "instrumentation")]
+        {
+            self.counts.allocations += 1;
+        }
+    }
+
+    // Add the counts of another heap, which is melded into this heap.
+    #[inline(always)]
+    pub(crate) fn absorb(&mut self, other: &Counters) {
+        #[cfg(feature = "instrumentation")]
+        self.counts.add(&other.counts);
+    }
+
+    #[cfg(feature = "instrumentation")]
+    pub(crate) fn get(&self) -> OpCounters {
+        self.counts
+    }
+
+    #[cfg(feature = "instrumentation")]
+    pub(crate) fn reset(&mut self) {
+        self.counts = OpCounters::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    #[cfg(not(feature = "instrumentation"))]
+    fn zero_cost_test() {
+        use crate::{Counters, LeftistHeap, PairingHeap, SkewHeap};
+
+        assert_eq!(size_of::<Counters>(), 0);
+        assert_eq!(size_of::<SkewHeap<u32>>(), size_of::<usize>());
+        assert_eq!(size_of::<LeftistHeap<u32>>(), size_of::<usize>());
+        assert_eq!(size_of::<PairingHeap<u32>>(), 2 * size_of::<usize>());
+    }
+
+    #[test]
+    #[cfg(feature = "instrumentation")]
+    #[cfg_attr(feature = "debug-invariants", ignore)]
+    fn pairing_heap_test() {
+        use crate::{OpCounters, PairingHeap};
+
+        let n = 1 << 16;
+        let mut heap = PairingHeap::new();
+        for i in 0..n {
+            heap.push(i);
+        }
+        // Each push links a singleton to the root.
+        let expected = OpCounters {
+            comparisons: n - 1,
+            melds: n - 1,
+            meld_path: n - 1,
+            sibling_passes: 0,
+            allocations: n,
+        };
+        assert_eq!(heap.counters(), expected);
+
+        // The first pop links all children of the root in two passes.
+        heap.reset_counters();
+        heap.pop();
+        let counters = heap.counters();
+        assert_eq!(counters.sibling_passes, 2);
+        assert_eq!(counters.comparisons, n - 2);
+
+        // The other pops take O(log n) amortized comparisons.
+        heap.reset_counters();
+        while heap.pop().is_some() {}
+        let counters = heap.counters();
+        assert!(counters.comparisons <= 4 * n * 16);
+        assert_eq!(counters.allocations, 0);
+    }
+
+    #[test]
+    #[cfg(feature = "instrumentation")]
+    #[cfg_attr(feature = "debug-invariants", ignore)]
+    fn leftist_heap_test() {
+        use crate::{LeftistHeap, SkewHeap};
+
+        let n = 1 << 16;
+        let mut leftist = LeftistHeap::new();
+        let mut skew = SkewHeap::new();
+        for _ in 0..n {
+            let x = rand::random::<u32>();
+            leftist.push(x);
+            skew.push(x);
+        }
+        for _ in 0..n {
+            leftist.pop();
+            skew.pop();
+        }
+        // The meld paths of a leftist heap are at most 2 log n long, and those of a skew heap are
+        // O(log n) amortized.
+        let counters = leftist.counters();
+        assert_eq!(counters.allocations, n);
+        assert!(counters.meld_path <= 2 * n * 2 * 16);
+        assert_eq!(counters.meld_path, counters.comparisons);
+        let counters = skew.counters();
+        assert!(counters.meld_path <= 2 * n * 3 * 16);
+
+        // Melding two heaps adds up their counters. Melds with an empty tree are not counted.
+        let mut heap1 = LeftistHeap::new();
+        let mut heap2 = LeftistHeap::new();
+        heap1.push(1);
+        heap2.push(2);
+        heap2.push(3);
+        let heap = LeftistHeap::meld(heap1, heap2);
+        assert_eq!(heap.counters().allocations, 3);
+        assert_eq!(heap.counters().melds, 2);
+    }
+}
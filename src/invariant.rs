@@ -0,0 +1,66 @@
+#![forbid(unsafe_code)]
+
+use core::fmt;
+
+/// The error returned by `validate` when the structure of a heap is broken,
+/// typically by an inconsistent `Ord` implementation or by mutating elements through interior mutability.
+///
+/// `depth` is the depth of the offending node, where the root is at depth 0.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InvariantError {
+    /// A node is less than its parent.
+    HeapOrder {
+        /// The depth of the node.
+        depth: usize,
+    },
+    /// The stored rank of a node is not one plus the smaller rank of its children.
+    Rank {
+        /// The depth of the node.
+        depth: usize,
+        /// The rank computed from the children.
+        expected: usize,
+        /// The stored rank.
+        actual: usize,
+    },
+    /// The rank of the left child of a node is less than the rank of the right child.
+    Leftist {
+        /// The depth of the node.
+        depth: usize,
+    },
+    /// The root of a tree has siblings.
+    RootSibling,
+    /// A tree in the auxiliary list has children, the head of the list is not the minimum,
+    /// or the list is not empty although the pairing strategy does not use it.
+    AuxiliaryList {
+        /// The position of the tree in the list.
+        position: usize,
+    },
+}
+
+impl fmt::Display for InvariantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvariantError::HeapOrder { depth } => {
+                write!(f, "node at depth {depth} is less than its parent")
+            }
+            InvariantError::Rank {
+                depth,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "node at depth {depth} has rank {actual}, but its children imply rank {expected}"
+            ),
+            InvariantError::Leftist { depth } => write!(
+                f,
+                "node at depth {depth} has a left child of smaller rank than its right child"
+            ),
+            InvariantError::RootSibling => f.write_str("root has siblings"),
+            InvariantError::AuxiliaryList { position } => {
+                write!(f, "auxiliary list is broken at position {position}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for InvariantError {}
@@ -0,0 +1,276 @@
+#![forbid(unsafe_code)]
+
+use core::cmp::Ordering;
+
+/// `AdditiveKey` is a key type of [`LazyLeftistHeap`](crate::LazyLeftistHeap) and
+/// [`LazySkewHeap`](crate::LazySkewHeap), to which an offset can be added.
+///
+/// Adding the same offset to two keys must not change their order.
+/// It is implemented for the primitive integer types, whose `add` panics on overflow in debug builds.
+pub trait AdditiveKey: Copy + Ord {
+    /// The offset which does not change a key.
+    const ZERO: Self;
+
+    /// Returns the sum of `self` and `delta`.
+    fn add(self, delta: Self) -> Self;
+}
+
+macro_rules! impl_additive_key {
+    ($($t:ty),*) => {
+        $(
+            impl AdditiveKey for $t {
+                const ZERO: Self = 0;
+
+                fn add(self, delta: Self) -> Self {
+                    self + delta
+                }
+            }
+        )*
+    };
+}
+
+impl_additive_key!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+// `Lazy` is an update of all elements of a subtree, which is kept at the root of the subtree until
+// the node is visited by `meld` or `pop`, and then pushed down to its children.
+// It is attached to the nodes of `LeftistHeap` and `SkewHeap` like `Aggregate`; `()` is no update.
+pub(crate) trait Lazy<T> {
+    // The pending update of the descendants of a node
+    type Pending;
+
+    // Returns no update.
+    fn none() -> Self::Pending;
+
+    // Takes the update out of `pending` and leaves no update, or returns `None` if there is no update.
+    fn take(pending: &mut Self::Pending) -> Option<Self::Pending>;
+
+    // Applies `update` to a node: to its element now, and to its descendants later.
+    fn apply(update: &Self::Pending, value: &mut T, pending: &mut Self::Pending);
+}
+
+impl<T> Lazy<T> for () {
+    type Pending = ();
+
+    fn none() {}
+
+    fn take(_pending: &mut ()) -> Option<()> {
+        None
+    }
+
+    fn apply(_update: &(), _value: &mut T, _pending: &mut ()) {}
+}
+
+// `AddAll` adds an offset to the keys of the entries of a subtree.
+pub(crate) struct AddAll;
+
+impl<K: AdditiveKey, V> Lazy<Entry<K, V>> for AddAll {
+    type Pending = K;
+
+    fn none() -> K {
+        K::ZERO
+    }
+
+    fn take(pending: &mut K) -> Option<K> {
+        (*pending != K::ZERO).then(|| core::mem::replace(pending, K::ZERO))
+    }
+
+    fn apply(update: &K, entry: &mut Entry<K, V>, pending: &mut K) {
+        entry.0 .0 = entry.0 .0.add(*update);
+        *pending = pending.add(*update);
+    }
+}
+
+// An entry of the lazy heaps. Only the keys are compared, so that the values need not be `Ord`.
+pub(crate) struct Entry<K, V>(pub(crate) (K, V));
+
+impl<K: Ord, V> PartialEq for Entry<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 .0 == other.0 .0
+    }
+}
+
+impl<K: Ord, V> Eq for Entry<K, V> {}
+
+impl<K: Ord, V> PartialOrd for Entry<K, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Ord, V> Ord for Entry<K, V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0 .0.cmp(&other.0 .0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use crate::{LazyLeftistHeap, LazySkewHeap, MeldableHeap};
+
+    // The operations of the lazy heaps which are not in `MeldableHeap`
+    trait LazyHeap: MeldableHeap<(i64, u32)> {
+        fn add_all(&mut self, delta: i64);
+        fn entries(&self) -> Vec<(i64, u32)>;
+    }
+
+    impl LazyHeap for LazyLeftistHeap<i64, u32> {
+        fn add_all(&mut self, delta: i64) {
+            self.add_all(delta)
+        }
+
+        fn entries(&self) -> Vec<(i64, u32)> {
+            self.iter().map(|(key, &value)| (key, value)).collect()
+        }
+    }
+
+    impl LazyHeap for LazySkewHeap<i64, u32> {
+        fn add_all(&mut self, delta: i64) {
+            self.add_all(delta)
+        }
+
+        fn entries(&self) -> Vec<(i64, u32)> {
+            self.iter().map(|(key, &value)| (key, value)).collect()
+        }
+    }
+
+    fn check_basic<H: LazyHeap>() {
+        let mut heap = H::default();
+        for (i, x) in [3, 1, 4, 1, 5, 9, 2, 6].into_iter().enumerate() {
+            heap.push((x, i as u32));
+        }
+        assert_eq!(heap.pop().map(|entry| entry.0), Some(1));
+        heap.add_all(10);
+        heap.push((12, 8));
+        assert_eq!(heap.peek().map(|entry| entry.0), Some(11));
+        heap.add_all(-5);
+
+        let mut keys = Vec::new();
+        while let Some((key, _)) = heap.pop() {
+            keys.push(key);
+        }
+        assert_eq!(keys, vec![6, 7, 7, 8, 9, 10, 11, 14]);
+    }
+
+    fn check_deep<H: LazyHeap>() {
+        let n = 1000000;
+        let mut heap = H::default();
+        for i in 0..n {
+            heap.push((n - i, 0));
+            heap.add_all(1);
+        }
+        // The key pushed at step i has been shifted n - i times.
+        assert_eq!(heap.pop(), Some((2, 0)));
+        drop(heap);
+    }
+
+    fn check_iter<H: LazyHeap>()
+    where
+        Vec<(i64, u32)>: From<H>,
+    {
+        let mut heap1 = H::default();
+        let mut heap2 = H::default();
+        for x in 0..10 {
+            heap1.push((x, x as u32));
+            heap2.push((x, x as u32 + 10));
+        }
+        heap1.add_all(100);
+        let mut heap = H::meld(heap1, heap2);
+        heap.pop();
+        heap.add_all(1);
+
+        let mut actual = heap.entries();
+        actual.sort();
+        let mut expected: Vec<_> = (1..10).map(|x| (x + 1, x as u32 + 10)).collect();
+        expected.extend((0..10).map(|x| (x + 101, x as u32)));
+        assert_eq!(actual, expected);
+
+        let mut entries = Vec::from(heap);
+        entries.sort();
+        assert_eq!(entries, expected);
+    }
+
+    fn check_model<H: LazyHeap>() {
+        for _ in 0..1000 {
+            // The model is a list of heaps of (key, id) pairs.
+            let mut heaps: Vec<H> = Vec::new();
+            let mut expected: Vec<Vec<(i64, u32)>> = Vec::new();
+            for id in 0..100 {
+                if heaps.is_empty() {
+                    heaps.push(H::default());
+                    expected.push(Vec::new());
+                }
+                let i = rand::random::<usize>() % heaps.len();
+                match rand::random::<u32>() % 5 {
+                    0 | 1 => {
+                        let key = (rand::random::<u32>() % 100) as i64;
+                        heaps[i].push((key, id));
+                        expected[i].push((key, id));
+                    }
+                    2 => {
+                        let actual = heaps[i].pop();
+                        let min = expected[i].iter().map(|entry| entry.0).min();
+                        assert_eq!(actual.map(|entry| entry.0), min);
+                        // The entries with the same key may be popped in any order.
+                        expected[i].retain(|&entry| Some(entry) != actual);
+                    }
+                    3 => {
+                        let delta = (rand::random::<u32>() % 21) as i64 - 10;
+                        heaps[i].add_all(delta);
+                        expected[i].iter_mut().for_each(|entry| entry.0 += delta);
+                    }
+                    4 => {
+                        let j = rand::random::<usize>() % (heaps.len() + 1);
+                        if i != j && j < heaps.len() {
+                            let heap2 = heaps.swap_remove(i.max(j));
+                            let heap1 = heaps.swap_remove(i.min(j));
+                            heaps.push(H::meld(heap1, heap2));
+                            let mut entries = expected.swap_remove(i.max(j));
+                            entries.extend(expected.swap_remove(i.min(j)));
+                            expected.push(entries);
+                        } else {
+                            heaps.push(H::default());
+                            expected.push(Vec::new());
+                        }
+                    }
+                    _ => unreachable!(),
+                }
+                for (heap, entries) in heaps.iter().zip(&expected) {
+                    let min = entries.iter().map(|entry| entry.0).min();
+                    assert_eq!(heap.peek().map(|entry| entry.0), min);
+                    let mut actual = heap.entries();
+                    actual.sort();
+                    let mut entries = entries.clone();
+                    entries.sort();
+                    assert_eq!(actual, entries);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn basic_test() {
+        check_basic::<LazyLeftistHeap<i64, u32>>();
+        check_basic::<LazySkewHeap<i64, u32>>();
+    }
+
+    #[test]
+    fn large_drop_test() {
+        check_deep::<LazyLeftistHeap<i64, u32>>();
+        check_deep::<LazySkewHeap<i64, u32>>();
+    }
+
+    #[test]
+    fn iter_test() {
+        check_iter::<LazyLeftistHeap<i64, u32>>();
+        check_iter::<LazySkewHeap<i64, u32>>();
+    }
+
+    #[test]
+    fn randomized_test() {
+        check_model::<LazyLeftistHeap<i64, u32>>();
+        check_model::<LazySkewHeap<i64, u32>>();
+    }
+}
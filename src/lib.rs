@@ -1,6 +1,31 @@
 #![no_std]
 extern crate alloc;
 
+mod heap;
+pub use heap::*;
+
+mod invariant;
+pub use invariant::*;
+
+mod aggregate;
+pub use aggregate::*;
+
+mod lazy;
+pub use lazy::*;
+
+mod stats;
+pub use stats::*;
+
+mod export;
+
+mod binary_tree;
+
+mod instrumentation;
+pub use instrumentation::*;
+
+mod random;
+pub use random::*;
+
 mod skew_heap;
 pub use skew_heap::*;
 
@@ -9,3 +34,54 @@ pub use leftist_heap::*;
 
 mod pairing_heap;
 pub use pairing_heap::*;
+
+mod pairing_strategy;
+pub use pairing_strategy::*;
+
+mod rank_pairing_heap;
+pub use rank_pairing_heap::*;
+
+mod hollow_heap;
+pub use hollow_heap::*;
+
+mod skew_binomial_heap;
+pub use skew_binomial_heap::*;
+
+mod weight_biased_leftist_heap;
+pub use weight_biased_leftist_heap::*;
+
+mod randomized_meldable_heap;
+pub use randomized_meldable_heap::*;
+
+mod bottom_up_skew_heap;
+pub use bottom_up_skew_heap::*;
+
+mod smooth_heap;
+pub use smooth_heap::*;
+
+mod soft_heap;
+pub use soft_heap::*;
+
+mod double_ended_pairing_heap;
+pub use double_ended_pairing_heap::*;
+
+mod fibonacci_heap;
+pub use fibonacci_heap::*;
+
+mod radix_heap;
+pub use radix_heap::*;
+
+mod calendar_queue;
+pub use calendar_queue::*;
+
+mod any_heap;
+pub use any_heap::*;
+
+mod adaptive_heap;
+pub use adaptive_heap::*;
+
+mod trace;
+pub use trace::*;
+
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
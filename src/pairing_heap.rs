@@ -1,46 +1,87 @@
 #![forbid(unsafe_code)]
 
-use alloc::{boxed::Box, vec::Vec};
+use core::{cmp::Reverse, fmt::Debug, marker::PhantomData};
+
+use alloc::{boxed::Box, collections::BinaryHeap, string::String, vec::Vec};
+
+use crate::{
+    Aggregate, Counters, HeapStats, InvariantError, LeftistHeap, MeldableHeap, Multipass,
+    PairingStrategy, SiblingList, SkewHeap, StatsBuilder, TwoPass,
+};
 
 /// `PairingHeap` is a priority queue implemented with pairing heap.
 /// `PairingHeap` is a **min-heap**, which means that the minimum element is popped first.
 ///
+/// The way to link the children of the popped root is selected by the type parameter `S`
+/// ([`TwoPass`], [`Multipass`], [`FrontToBack`](crate::FrontToBack), [`BackToFront`](crate::BackToFront)
+/// or [`AuxiliaryTwoPass`](crate::AuxiliaryTwoPass)).
+/// Use `PairingHeap::<T, Multipass>::default()` to construct a heap with a strategy other than [`TwoPass`].
+///
 /// # Reference
 /// Fredman, Michael L.; Sedgewick, Robert; Sleator, Daniel D.; Tarjan, Robert E. (1986).
 /// ["The pairing heap: a new form of self-adjusting heap"][Fredman-Sedgewick-Sleator-Tarjan-1986]
 ///
 /// [Fredman-Sedgewick-Sleator-Tarjan-1986]: https://www.cs.cmu.edu/~sleator/papers/pairing-heaps.pdf
-pub struct PairingHeap<T: Ord> {
+pub struct PairingHeap<T: Ord, S: PairingStrategy = TwoPass> {
     root: Option<Box<Node<T>>>,
+    // Trees which are not linked to `root` yet. It is used only if `S::AUXILIARY` is true.
+    // The tree with the minimum root is at the head of the list.
+    aux: Option<Box<Node<T>>>,
+    strategy: PhantomData<S>,
+    counters: Counters,
 }
 
 impl<T: Ord> PairingHeap<T> {
     pub fn new() -> Self {
-        Self { root: None }
+        Self::default()
     }
+}
 
+impl<T: Ord, S: PairingStrategy> PairingHeap<T, S> {
     pub fn is_empty(&self) -> bool {
-        self.root.is_none()
+        self.root.is_none() && self.aux.is_none()
     }
 
     pub fn push(&mut self, value: T) {
-        self.root = Node::meld(self.root.take(), Node::singleton(value))
+        self.counters.allocation();
+        if S::AUXILIARY {
+            Node::push_aux(&mut self.aux, value, &mut self.counters);
+        } else {
+            let root = self.root.take();
+            self.root = Node::meld(root, Node::singleton(value), &mut self.counters);
+        }
+        self.debug_validate();
     }
 
     pub fn pop(&mut self) -> Option<T> {
+        self.flush_aux();
         let root = self.root.take()?;
         let value = root.value;
-        self.root = Node::meld_siblings(root.first_child);
+        self.root = Node::meld_siblings::<S>(root.first_child, &mut self.counters);
+        self.debug_validate();
         Some(value)
     }
 
     pub fn peek(&self) -> Option<&T> {
-        self.root.as_ref().map(|node| &node.value)
+        let root = self.root.as_ref().map(|node| &node.value);
+        let aux = self.aux.as_ref().map(|node| &node.value);
+        root.into_iter().chain(aux).min()
     }
 
-    pub fn meld(mut heap1: PairingHeap<T>, mut heap2: PairingHeap<T>) -> PairingHeap<T> {
-        let root = Node::meld(heap1.root.take(), heap2.root.take());
-        Self { root }
+    pub fn meld(mut heap1: Self, mut heap2: Self) -> Self {
+        heap1.flush_aux();
+        heap2.flush_aux();
+        let mut counters = heap1.counters;
+        counters.absorb(&heap2.counters);
+        let root = Node::meld(heap1.root.take(), heap2.root.take(), &mut counters);
+        let heap = Self {
+            root,
+            aux: None,
+            strategy: PhantomData,
+            counters,
+        };
+        heap.debug_validate();
+        heap
     }
 
     pub fn iter(&self) -> impl Iterator<Item = &T> {
@@ -48,21 +89,262 @@ impl<T: Ord> PairingHeap<T> {
         if let Some(ref node) = self.root {
             stack.push(node.as_ref());
         }
+        let mut it = &self.aux;
+        while let Some(node) = it {
+            it = &node.next_sibling;
+            stack.push(node);
+        }
         Iter { stack }
     }
-}
 
-// We need to implement `drop` for PairingHeap because auto-generated `drop` would cause stack overflow
-// (the depth of the tree can be O(n) in the worst case).
-impl<T: Ord> Drop for PairingHeap<T> {
-    // Visit all nodes in depth-first order, and drop them one-by-one.
+    /// Returns an iterator that visits the elements not greater than `bound`, in arbitrary order.
+    /// Subtrees whose roots are greater than `bound` are skipped by the heap order,
+    /// but all children of a visited node and all trees in the auxiliary list are compared with `bound`.
+    /// O(k + c) time, where k is the number of elements not greater than `bound`
+    /// and c is the number of their children plus the length of the auxiliary list.
+    pub fn iter_below<'a>(&'a self, bound: &'a T) -> impl Iterator<Item = &'a T> + 'a {
+        let mut stack = Vec::new();
+        stack.extend(self.root.as_deref());
+        let mut it = &self.aux;
+        while let Some(node) = it {
+            it = &node.next_sibling;
+            stack.push(node.as_ref());
+        }
+        core::iter::from_fn(move || loop {
+            let node = stack.pop()?;
+            if node.value <= *bound {
+                let mut it = &node.first_child;
+                while let Some(child) = it {
+                    it = &child.next_sibling;
+                    stack.push(child.as_ref());
+                }
+                return Some(&node.value);
+            }
+        })
+    }
+
+    /// Returns the number of elements not greater than `bound`.
+    /// O(k + c) time, as [`iter_below`](Self::iter_below).
+    pub fn count_below(&self, bound: &T) -> usize {
+        self.iter_below(bound).count()
+    }
+
+    /// Removes the elements not greater than `bound` from the heap, and returns them as a new heap.
+    /// The removed nodes are detached by a depth-first search which stops at the nodes greater than `bound`,
+    /// and both heaps are rebuilt by linking the pieces with [`Multipass`].
+    /// O(k + c) time, as [`iter_below`](Self::iter_below).
+    pub fn split_off_below(&mut self, bound: &T) -> Self {
+        // Both results are built as sibling lists, and linked at the end.
+        let mut below: Option<Box<Node<T>>> = None;
+        let mut above: Option<Box<Node<T>>> = None;
+        let mut stack = Vec::new();
+        stack.extend(self.root.take());
+        let mut it = self.aux.take();
+        while let Some(mut node) = it {
+            it = node.next_sibling.take();
+            stack.push(node);
+        }
+        while let Some(mut node) = stack.pop() {
+            if node.value > *bound {
+                node.next_sibling = above.take();
+                above = Some(node);
+                continue;
+            }
+            let mut it = node.first_child.take();
+            while let Some(mut child) = it {
+                it = child.next_sibling.take();
+                stack.push(child);
+            }
+            node.next_sibling = below.take();
+            below = Some(node);
+        }
+        let mut siblings = Siblings(&mut self.counters, PhantomData);
+        self.root = above.map(|first| Multipass::link_all(&mut siblings, first));
+        let root = below.map(|first| Multipass::link_all(&mut siblings, first));
+        self.debug_validate();
+        let heap = Self {
+            root,
+            aux: None,
+            strategy: PhantomData,
+            counters: Counters::default(),
+        };
+        heap.debug_validate();
+        heap
+    }
+
+    /// Rewrites all elements in place with `f`, keeping the shape of the trees.
+    /// `f` must preserve the order of the elements, i.e. `a <= b` must imply `f(a) <= f(b)`;
+    /// otherwise the heap order is broken. Use [`reprioritize`](Self::reprioritize) for other functions.
+    /// O(n) time.
+    pub fn map_monotone(&mut self, mut f: impl FnMut(&mut T)) {
+        let mut stack = Vec::new();
+        stack.extend(self.root.as_deref_mut());
+        stack.extend(self.aux.as_deref_mut());
+        while let Some(node) = stack.pop() {
+            f(&mut node.value);
+            stack.extend(node.first_child.as_deref_mut());
+            stack.extend(node.next_sibling.as_deref_mut());
+        }
+        self.debug_validate();
+    }
+
+    /// Rewrites all elements with `f`, and rebuilds the heap by linking the nodes with [`Multipass`].
+    /// Unlike [`map_monotone`](Self::map_monotone), `f` may change the order of the elements.
+    /// The nodes are reused, so no memory is allocated for them.
+    /// O(n) time.
+    pub fn reprioritize(&mut self, mut f: impl FnMut(&mut T)) {
+        // The nodes are collected into a sibling list as singletons, and linked at the end.
+        let mut list: Option<Box<Node<T>>> = None;
+        let mut stack = Vec::new();
+        stack.extend(self.root.take());
+        stack.extend(self.aux.take());
+        while let Some(mut node) = stack.pop() {
+            stack.extend(node.first_child.take());
+            stack.extend(node.next_sibling.take());
+            f(&mut node.value);
+            node.next_sibling = list.take();
+            list = Some(node);
+        }
+        let mut siblings = Siblings(&mut self.counters, PhantomData);
+        self.root = list.map(|first| Multipass::link_all(&mut siblings, first));
+        self.debug_validate();
+    }
+
+    /// Returns statistics of the shape of the heap.
+    /// The trees in the auxiliary list are counted as separate trees.
+    /// O(n) time.
+    pub fn stats(&self) -> HeapStats {
+        let mut builder = StatsBuilder::default();
+        let mut stack: Vec<_> = self
+            .cursor()
+            .into_iter()
+            .map(|cursor| (cursor, 1))
+            .collect();
+        let aux = core::iter::successors(self.aux_cursor(), |cursor| cursor.next_sibling());
+        stack.extend(aux.map(|cursor| (cursor, 1)));
+        while let Some((cursor, depth)) = stack.pop() {
+            let len = stack.len();
+            stack.extend(cursor.children().map(|child| (child, depth + 1)));
+            builder.node(depth, stack.len() - len);
+        }
+        let right_spine =
+            core::iter::successors(self.cursor(), |cursor| cursor.children().last()).count();
+        builder.finish(right_spine, size_of::<Self>(), size_of::<Node<T>>())
+    }
+
+    /// Returns the operation counters of the heap.
+    /// O(1) time.
+    #[cfg(feature = "instrumentation")]
+    pub fn counters(&self) -> crate::OpCounters {
+        self.counters.get()
+    }
+
+    /// Resets the operation counters of the heap to zero.
+    /// O(1) time.
+    #[cfg(feature = "instrumentation")]
+    pub fn reset_counters(&mut self) {
+        self.counters.reset();
+    }
+
+    /// Returns a cursor at the root of the main tree, or `None` if it is empty.
+    /// O(1) time.
+    pub fn cursor(&self) -> Option<PairingHeapCursor<'_, T>> {
+        self.root.as_deref().map(|node| PairingHeapCursor { node })
+    }
+
+    /// Returns a cursor at the first tree in the auxiliary list, or `None` if it is empty.
+    /// The other trees in the list are its siblings.
+    /// O(1) time.
+    pub fn aux_cursor(&self) -> Option<PairingHeapCursor<'_, T>> {
+        self.aux.as_deref().map(|node| PairingHeapCursor { node })
+    }
+
+    /// Returns the trees of the heap in Graphviz DOT format.
+    /// Nodes are labeled with the `Debug` representation of their elements.
+    /// Edges to the first children are solid, and edges to the next siblings are dashed.
+    /// If `max_depth` is given, the children of the nodes at that depth (the roots are at depth 0) are replaced with `...`.
+    /// O(n) time.
+    pub fn to_dot(&self, max_depth: Option<usize>) -> String
+    where
+        T: Debug,
+    {
+        crate::export::pairing_to_dot(self.cursor(), self.aux_cursor(), max_depth)
+    }
+
+    /// Returns the trees of the heap in JSON, as `{"root": node or null, "aux": [node, ...]}`.
+    /// Each node is `{"value": string, "children": [node, ...]}`, where `value` is the `Debug` representation of the element.
+    /// If `max_depth` is given, the nodes at that depth (the roots are at depth 0) have empty `children`
+    /// and `"truncated": true` if they have children.
+    /// O(n) time.
+    pub fn to_json(&self, max_depth: Option<usize>) -> String
+    where
+        T: Debug,
+    {
+        crate::export::pairing_to_json(self.cursor(), self.aux_cursor(), max_depth)
+    }
+
+    /// Checks the heap order of all nodes and the sanity of the sibling lists,
+    /// and returns the first violation found.
+    /// O(n) time.
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        let mut stack = Vec::new();
+        if let Some(ref root) = self.root {
+            if root.next_sibling.is_some() {
+                return Err(InvariantError::RootSibling);
+            }
+            stack.push((root.as_ref(), 0));
+        }
+        while let Some((node, depth)) = stack.pop() {
+            let mut it = &node.first_child;
+            while let Some(child) = it {
+                if child.value < node.value {
+                    return Err(InvariantError::HeapOrder { depth: depth + 1 });
+                }
+                stack.push((child.as_ref(), depth + 1));
+                it = &child.next_sibling;
+            }
+        }
+
+        // The auxiliary list consists of singleton trees, and the minimum is at the head.
+        if !S::AUXILIARY && self.aux.is_some() {
+            return Err(InvariantError::AuxiliaryList { position: 0 });
+        }
+        let mut it = &self.aux;
+        let mut position = 0;
+        while let Some(node) = it {
+            let head = self.aux.as_ref().unwrap();
+            if node.first_child.is_some() || node.value < head.value {
+                return Err(InvariantError::AuxiliaryList { position });
+            }
+            it = &node.next_sibling;
+            position += 1;
+        }
+        Ok(())
+    }
+
+    // Validate the heap after a mutating operation if the `debug-invariants` feature is enabled.
+    fn debug_validate(&self) {
+        #[cfg(feature = "debug-invariants")]
+        if let Err(err) = self.validate() {
+            panic!("{err}");
+        }
+    }
+
+    // Move all elements out of the heap in arbitrary order, and pass them to `f` one-by-one.
+    // Visit all nodes in depth-first order.
     //
     // This implementation reuses heap nodes to create a stack structure.
     // Therefore, it consumes only O(1) memory except for the heap itself.
-    fn drop(&mut self) {
+    pub(crate) fn drain_with(&mut self, mut f: impl FnMut(T)) {
         let mut stack_top = None;
         let mut it = self.root.take();
 
+        // The auxiliary list is visited as siblings of the root.
+        match &mut it {
+            Some(root) => root.next_sibling = self.aux.take(),
+            None => it = self.aux.take(),
+        }
+
         loop {
             while let Some(mut node) = it {
                 let next_sibling = node.next_sibling;
@@ -77,11 +359,104 @@ impl<T: Ord> Drop for PairingHeap<T> {
 
             // pop a node from the stack
             let Some(top) = stack_top else { break };
-            stack_top = top.next_sibling;
-            it = top.first_child;
+            let Node {
+                value,
+                first_child,
+                next_sibling,
+            } = *top;
+            stack_top = next_sibling;
+            it = first_child;
 
             // `top` is deallocated here
+            f(value);
+        }
+    }
+
+    // Link the trees in the auxiliary list to the main tree.
+    fn flush_aux(&mut self) {
+        let Some(aux) = self.aux.take() else { return };
+        let tree = Multipass::link_all(&mut Siblings(&mut self.counters, PhantomData), aux);
+        self.root = Node::meld(self.root.take(), Some(tree), &mut self.counters);
+    }
+}
+
+impl<T: Ord, S: PairingStrategy> Default for PairingHeap<T, S> {
+    fn default() -> Self {
+        Self {
+            root: None,
+            aux: None,
+            strategy: PhantomData,
+            counters: Counters::default(),
+        }
+    }
+}
+
+#[rustfmt::skip]
+impl<T: Ord, S: PairingStrategy> MeldableHeap<T> for PairingHeap<T, S> {
+    fn is_empty(&self) -> bool { self.is_empty() }
+    fn push(&mut self, value: T) { self.push(value) }
+    fn pop(&mut self) -> Option<T> { self.pop() }
+    fn peek(&self) -> Option<&T> { self.peek() }
+    fn meld(heap1: Self, heap2: Self) -> Self { Self::meld(heap1, heap2) }
+}
+
+impl<T: Ord, S: PairingStrategy> From<Vec<T>> for PairingHeap<T, S> {
+    /// Builds a heap from the elements of a vector.
+    /// O(n) time.
+    fn from(values: Vec<T>) -> Self {
+        let mut heap = Self::default();
+        for value in values {
+            heap.push(value);
+        }
+        heap
+    }
+}
+
+impl<T: Ord, S: PairingStrategy> From<BinaryHeap<Reverse<T>>> for PairingHeap<T, S> {
+    /// O(n) time.
+    fn from(heap: BinaryHeap<Reverse<T>>) -> Self {
+        let mut result = Self::default();
+        for Reverse(value) in heap {
+            result.push(value);
         }
+        result
+    }
+}
+
+impl<T: Ord, S: PairingStrategy, A: Aggregate<T>> From<LeftistHeap<T, A>> for PairingHeap<T, S> {
+    /// O(n) time.
+    fn from(mut heap: LeftistHeap<T, A>) -> Self {
+        let mut result = Self::default();
+        heap.drain_with(|value| result.push(value));
+        result
+    }
+}
+
+impl<T: Ord, S: PairingStrategy, A: Aggregate<T>> From<SkewHeap<T, A>> for PairingHeap<T, S> {
+    /// O(n) time.
+    fn from(mut heap: SkewHeap<T, A>) -> Self {
+        let mut result = Self::default();
+        heap.drain_with(|value| result.push(value));
+        result
+    }
+}
+
+impl<T: Ord, S: PairingStrategy> From<PairingHeap<T, S>> for Vec<T> {
+    /// Returns the elements of the heap in arbitrary order.
+    /// O(n) time.
+    fn from(mut heap: PairingHeap<T, S>) -> Self {
+        let mut values = Vec::new();
+        heap.drain_with(|value| values.push(value));
+        values
+    }
+}
+
+// We need to implement `drop` for PairingHeap because auto-generated `drop` would cause stack overflow
+// (the depth of the tree can be O(n) in the worst case).
+impl<T: Ord, S: PairingStrategy> Drop for PairingHeap<T, S> {
+    // Visit all nodes in depth-first order, and drop them one-by-one.
+    fn drop(&mut self) {
+        self.drain_with(drop);
     }
 
     /*
@@ -115,16 +490,28 @@ impl<T: Ord> Node<T> {
         }))
     }
 
-    fn meld(root1: Option<Box<Node<T>>>, root2: Option<Box<Node<T>>>) -> Option<Box<Node<T>>> {
+    fn meld(
+        root1: Option<Box<Node<T>>>,
+        root2: Option<Box<Node<T>>>,
+        counters: &mut Counters,
+    ) -> Option<Box<Node<T>>> {
         let (root1, root2) = match (root1, root2) {
             (None, root2) => return root2,
             (root1, None) => return root1,
             (Some(r1), Some(r2)) => (r1, r2),
         };
-        Some(Self::xmeld(root1, root2))
+        Some(Self::xmeld(root1, root2, counters))
     }
 
-    fn xmeld(mut root1: Box<Node<T>>, mut root2: Box<Node<T>>) -> Box<Node<T>> {
+    fn xmeld(
+        mut root1: Box<Node<T>>,
+        mut root2: Box<Node<T>>,
+        counters: &mut Counters,
+    ) -> Box<Node<T>> {
+        counters.meld();
+        counters.comparison();
+        counters.meld_path(1);
+
         // Ensure root1 <= root2
         if root1.value > root2.value {
             core::mem::swap(&mut root1, &mut root2);
@@ -137,32 +524,96 @@ impl<T: Ord> Node<T> {
         root1
     }
 
-    fn meld_siblings(heaps: Option<Box<Node<T>>>) -> Option<Box<Node<T>>> {
-        let mut it = heaps;
-        let mut stack = None;
-        // For each iteration, take 2 heaps from `heaps` and meld them.
-        while let Some(mut heap1) = it {
-            it = heap1.next_sibling.take();
-            let mut melt = if let Some(mut heap2) = it {
-                it = heap2.next_sibling.take();
-                Self::xmeld(heap1, heap2)
-            } else {
-                heap1
-            };
-            // Push `melt` to `stack`.
-            melt.next_sibling = stack;
-            stack = Some(melt);
-        }
-        // Meld all heaps in the stack.
-        let mut ret = None;
-        while let Some(mut h) = stack {
-            stack = h.next_sibling.take();
-            ret = Self::meld(Some(h), ret);
-        }
-        ret
+    // Insert a singleton node into the auxiliary list, keeping the minimum at the head.
+    fn push_aux(aux: &mut Option<Box<Node<T>>>, value: T, counters: &mut Counters) {
+        let mut node = Box::new(Self {
+            value,
+            first_child: None,
+            next_sibling: None,
+        });
+        if aux.is_some() {
+            counters.comparison();
+        }
+        match aux {
+            Some(head) if head.value <= node.value => {
+                node.next_sibling = head.next_sibling.take();
+                head.next_sibling = Some(node);
+            }
+            _ => {
+                node.next_sibling = aux.take();
+                *aux = Some(node);
+            }
+        }
+    }
+
+    fn meld_siblings<S: PairingStrategy>(
+        heaps: Option<Box<Node<T>>>,
+        counters: &mut Counters,
+    ) -> Option<Box<Node<T>>> {
+        heaps.map(|first| S::link_all(&mut Siblings(counters, PhantomData), first))
     }
 }
 
+// `Siblings` exposes lists of nodes linked by `next_sibling` to `PairingStrategy`.
+// It also counts the operations in the counters of the heap.
+struct Siblings<'a, T: Ord>(&'a mut Counters, PhantomData<T>);
+
+impl<T: Ord> SiblingList for Siblings<'_, T> {
+    type Tree = Box<Node<T>>;
+
+    fn take_next(&mut self, tree: &mut Self::Tree) -> Option<Self::Tree> {
+        tree.next_sibling.take()
+    }
+
+    fn set_next(&mut self, tree: &mut Self::Tree, next: Option<Self::Tree>) {
+        tree.next_sibling = next;
+    }
+
+    fn link(&mut self, tree1: Self::Tree, tree2: Self::Tree) -> Self::Tree {
+        Node::xmeld(tree1, tree2, self.0)
+    }
+
+    fn start_pass(&mut self) {
+        self.0.sibling_pass();
+    }
+}
+
+/// `PairingHeapCursor` is a read-only reference to a node of [`PairingHeap`],
+/// returned by [`PairingHeap::cursor`] and [`PairingHeap::aux_cursor`].
+pub struct PairingHeapCursor<'a, T: Ord> {
+    node: &'a Node<T>,
+}
+
+impl<'a, T: Ord> PairingHeapCursor<'a, T> {
+    /// Returns the element of the node.
+    pub fn value(&self) -> &'a T {
+        &self.node.value
+    }
+
+    /// Returns the first child of the node, or `None` if it has no children.
+    pub fn first_child(&self) -> Option<Self> {
+        self.node.first_child.as_deref().map(|node| Self { node })
+    }
+
+    /// Returns the next sibling of the node, or `None` if it is the last child.
+    pub fn next_sibling(&self) -> Option<Self> {
+        self.node.next_sibling.as_deref().map(|node| Self { node })
+    }
+
+    /// Returns an iterator over the children of the node, from the first child.
+    pub fn children(&self) -> impl Iterator<Item = PairingHeapCursor<'a, T>> {
+        core::iter::successors(self.first_child(), |cursor| cursor.next_sibling())
+    }
+}
+
+impl<T: Ord> Clone for PairingHeapCursor<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Ord> Copy for PairingHeapCursor<'_, T> {}
+
 struct Iter<'a, T: Ord> {
     stack: Vec<&'a Node<T>>,
 }
@@ -175,7 +626,7 @@ impl<'a, T: Ord> Iterator for Iter<'a, T> {
         let mut it = &node.first_child;
         while let Some(child) = it {
             it = &child.next_sibling;
-            self.stack.push(&child);
+            self.stack.push(child);
         }
         Some(&node.value)
     }
@@ -183,81 +634,256 @@ impl<'a, T: Ord> Iterator for Iter<'a, T> {
 
 #[cfg(test)]
 mod tests {
-    use alloc::collections::BinaryHeap;
     use alloc::vec;
     use alloc::vec::Vec;
-    use core::cmp::Reverse;
+    use core::cell::Cell;
 
-    use crate::PairingHeap;
+    use crate::testing::{self, ModelConfig};
+    use crate::{
+        AuxiliaryTwoPass, BackToFront, FrontToBack, InvariantError, Multipass, PairingHeap,
+        PairingStrategy, TwoPass,
+    };
 
     #[test]
     fn basic_test() {
+        testing::check_basic::<PairingHeap<u32>>();
+    }
+
+    #[test]
+    #[cfg_attr(feature = "debug-invariants", ignore)]
+    fn large_drop_test() {
+        testing::check_deep::<PairingHeap<u32>>(1000000);
+    }
+
+    #[test]
+    fn randomized_test() {
+        let config = ModelConfig {
+            seed: rand::random(),
+            ..ModelConfig::default()
+        };
+        testing::check_model::<PairingHeap<u32>>(&config);
+    }
+
+    fn strategy_test<S: PairingStrategy>() {
+        let config = ModelConfig {
+            seed: rand::random(),
+            ..ModelConfig::default()
+        };
+        testing::check_model::<PairingHeap<u32, S>>(&config);
+    }
+
+    #[test]
+    fn strategies_test() {
+        strategy_test::<TwoPass>();
+        strategy_test::<Multipass>();
+        strategy_test::<FrontToBack>();
+        strategy_test::<BackToFront>();
+        strategy_test::<AuxiliaryTwoPass>();
+    }
+
+    #[test]
+    #[cfg_attr(feature = "debug-invariants", ignore)]
+    fn auxiliary_large_drop_test() {
+        testing::check_deep::<PairingHeap<u32, AuxiliaryTwoPass>>(1000000);
+    }
+
+    #[test]
+    fn validate_test() {
         let mut heap = PairingHeap::new();
-        for x in [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5, 8, 9, 7, 9] {
-            heap.push(x);
+        for x in 0..100 {
+            heap.push(Cell::new(x));
         }
-        let mut actual = Vec::new();
-        while !heap.is_empty() {
-            let x = heap.pop().unwrap();
-            actual.push(x);
+        heap.pop();
+        assert_eq!(heap.validate(), Ok(()));
+
+        // Break the heap order through interior mutability.
+        heap.iter().max().unwrap().set(0);
+        assert!(matches!(
+            heap.validate(),
+            Err(InvariantError::HeapOrder { .. })
+        ));
+
+        let mut heap = PairingHeap::<_, AuxiliaryTwoPass>::default();
+        for x in 0..10 {
+            heap.push(Cell::new(x));
         }
-        let expected = vec![1, 1, 2, 3, 3, 4, 5, 5, 5, 6, 7, 8, 9, 9, 9];
-        assert_eq!(expected, actual);
+        assert_eq!(heap.validate(), Ok(()));
+        heap.iter().max().unwrap().set(-1);
+        assert!(matches!(
+            heap.validate(),
+            Err(InvariantError::AuxiliaryList { .. })
+        ));
     }
 
     #[test]
-    fn drop_test() {
+    fn stats_test() {
         let mut heap = PairingHeap::new();
-        for x in [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5, 8, 9, 7, 9] {
+        for x in 0..10 {
             heap.push(x);
         }
-        drop(heap);
+        // All nodes are children of the root.
+        let stats = heap.stats();
+        assert_eq!(stats.nodes, 10);
+        assert_eq!(stats.height, 2);
+        assert_eq!(stats.right_spine, 2);
+        assert_eq!(stats.max_root_degree, 9);
+        assert_eq!(stats.average_root_degree, 9.0);
+        assert_eq!(stats.max_degree, 9);
+        assert_eq!(stats.average_degree, 9.0);
+        assert!(stats.rank_counts.is_empty());
+
+        let root = heap.cursor().unwrap();
+        assert_eq!(root.value(), &0);
+        let children: Vec<_> = root.children().map(|child| *child.value()).collect();
+        assert_eq!(children, vec![9, 8, 7, 6, 5, 4, 3, 2, 1]);
+        assert!(root.next_sibling().is_none());
+        assert!(heap.aux_cursor().is_none());
+
+        let mut heap = PairingHeap::<_, AuxiliaryTwoPass>::default();
+        for x in 0..10 {
+            heap.push(x);
+        }
+        let stats = heap.stats();
+        assert_eq!(stats.nodes, 10);
+        assert_eq!(stats.height, 1);
+        assert!(heap.cursor().is_none());
+        let aux = core::iter::successors(heap.aux_cursor(), |cursor| cursor.next_sibling());
+        assert_eq!(aux.count(), 10);
+
+        // The main tree and two singletons in the auxiliary list are the roots.
+        heap.pop();
+        heap.push(20);
+        heap.push(21);
+        let stats = heap.stats();
+        let degree = heap.cursor().unwrap().children().count();
+        assert_eq!(stats.max_root_degree, degree);
+        assert_eq!(stats.average_root_degree, degree as f64 / 3.0);
     }
 
     #[test]
-    fn large_drop_test() {
-        let n = 1000000;
+    fn export_test() {
         let mut heap = PairingHeap::new();
-        for i in 0..n {
-            heap.push(i);
+        for x in [3, 1, 2, 0] {
+            heap.push(x);
+        }
+        heap.pop();
+        heap.push(5);
+        // Child edges are solid, and sibling edges are dashed.
+        let expected = r#"digraph {
+  n0 [label="1"];
+  n0 -> n1;
+  n1 [label="5"];
+  n1 -> n2 [style=dashed];
+  n2 [label="2"];
+  n2 -> n3 [style=dashed];
+  n3 [label="3"];
+}
+"#;
+        assert_eq!(heap.to_dot(None), expected);
+        let expected = r#"{"root":{"value":"1","children":[{"value":"5","children":[]},{"value":"2","children":[]},{"value":"3","children":[]}]},"aux":[]}"#;
+        assert_eq!(heap.to_json(None), expected);
+        let expected = r#"{"root":{"value":"1","children":[],"truncated":true},"aux":[]}"#;
+        assert_eq!(heap.to_json(Some(0)), expected);
+
+        let mut heap = PairingHeap::<_, AuxiliaryTwoPass>::default();
+        for x in [3, 1, 2] {
+            heap.push(x);
+        }
+        let expected = r#"{"root":null,"aux":[{"value":"1","children":[]},{"value":"2","children":[]},{"value":"3","children":[]}]}"#;
+        assert_eq!(heap.to_json(None), expected);
+    }
+
+    fn below_test_with<S: PairingStrategy>() {
+        for _ in 0..100 {
+            let mut heap = PairingHeap::<u32, S>::default();
+            let mut expected = Vec::new();
+            for _ in 0..rand::random::<u32>() % 100 {
+                let x = rand::random::<u32>() % 100;
+                heap.push(x);
+                expected.push(x);
+            }
+            // Pop a few elements to give the tree some shape.
+            expected.sort();
+            for x in expected.drain(..expected.len().min(3)) {
+                assert_eq!(heap.pop(), Some(x));
+            }
+            // Elements pushed after a pop stay in the auxiliary list of `PairingHeap<T, AuxiliaryTwoPass>`.
+            for _ in 0..10 {
+                let x = rand::random::<u32>() % 100;
+                heap.push(x);
+                expected.push(x);
+            }
+            expected.sort();
+
+            let bound = rand::random::<u32>() % 110;
+            let (below, above) = expected.split_at(expected.partition_point(|&x| x <= bound));
+            let mut actual: Vec<_> = heap.iter_below(&bound).copied().collect();
+            actual.sort();
+            assert_eq!(actual, below);
+            assert_eq!(heap.count_below(&bound), below.len());
+
+            let split = heap.split_off_below(&bound);
+            assert_eq!(heap.validate(), Ok(()));
+            assert_eq!(split.validate(), Ok(()));
+            for (mut heap, values) in [(split, below), (heap, above)] {
+                for &x in values {
+                    assert_eq!(heap.pop(), Some(x));
+                }
+                assert!(heap.is_empty());
+            }
         }
-        drop(heap);
     }
 
     #[test]
-    fn iter_test() {
-        let mut heap = PairingHeap::new();
-        for x in [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5, 8, 9, 7, 9] {
+    fn below_test() {
+        below_test_with::<TwoPass>();
+        below_test_with::<AuxiliaryTwoPass>();
+    }
+
+    fn map_test_with<S: PairingStrategy>() {
+        let mut heap = PairingHeap::<u64, S>::default();
+        for x in [3, 1, 4, 1, 5, 9, 2, 6] {
             heap.push(x);
         }
-        let mut actual: Vec<_> = heap.iter().copied().collect();
-        actual.sort();
-        let expected = vec![1, 1, 2, 3, 3, 4, 5, 5, 5, 6, 7, 8, 9, 9, 9];
-        assert_eq!(expected, actual);
+        heap.pop();
+        heap.push(7);
+        heap.map_monotone(|x| *x = *x * 10 + 1);
+        assert_eq!(heap.validate(), Ok(()));
+        assert_eq!(heap.peek(), Some(&11));
+
+        heap.reprioritize(|x| *x = 100 - *x);
+        assert_eq!(heap.validate(), Ok(()));
+        let mut values = Vec::new();
+        while let Some(x) = heap.pop() {
+            values.push(x);
+        }
+        assert_eq!(values, vec![9, 29, 39, 49, 59, 69, 79, 89]);
     }
 
-    #[test]
-    fn randomized_test() {
-        for _ in 0..1000 {
-            let mut heap = PairingHeap::new();
-            // BinaryHeap is max-heap. So, we need to push Reverse(x) to make it min-heap.
-            let mut expected = BinaryHeap::new();
-            for i in 0..100 {
-                match rand::random::<u32>() % 2 {
-                    0 => {
-                        heap.push(i);
-                        expected.push(Reverse(i));
-                    }
-                    1 => {
-                        let actual_x = heap.pop();
-                        let expected_x = expected.pop().map(|r| r.0);
-                        assert_eq!(actual_x, expected_x);
-                    }
-                    _ => unreachable!(),
-                }
-                assert_eq!(expected.is_empty(), heap.is_empty());
-                assert_eq!(expected.peek().map(|r| r.0), heap.peek().copied());
+    fn map_large_test_with<S: PairingStrategy>() {
+        let n = 1000000;
+        for values in [(0..n).collect::<Vec<_>>(), (0..n).rev().collect()] {
+            let mut heap = PairingHeap::<u64, S>::default();
+            for x in values {
+                heap.push(x);
             }
+            heap.map_monotone(|x| *x += 1);
+            assert_eq!(heap.pop(), Some(1));
+            heap.reprioritize(|x| *x = n - *x);
+            assert_eq!(heap.pop(), Some(0));
         }
     }
+
+    #[test]
+    fn map_test() {
+        map_test_with::<TwoPass>();
+        map_test_with::<AuxiliaryTwoPass>();
+    }
+
+    #[test]
+    #[cfg_attr(feature = "debug-invariants", ignore)]
+    fn map_large_test() {
+        map_large_test_with::<TwoPass>();
+        map_large_test_with::<AuxiliaryTwoPass>();
+    }
 }
@@ -0,0 +1,142 @@
+#![forbid(unsafe_code)]
+
+/// `SiblingList` gives a [`PairingStrategy`] access to a list of trees linked by their sibling pointers.
+pub trait SiblingList {
+    /// The type of a tree in the list.
+    type Tree;
+
+    /// Detaches the tree next to `tree` and returns it, or `None` if `tree` is the last one.
+    fn take_next(&mut self, tree: &mut Self::Tree) -> Option<Self::Tree>;
+
+    /// Sets the tree next to `tree`.
+    fn set_next(&mut self, tree: &mut Self::Tree, next: Option<Self::Tree>);
+
+    /// Links two trees into one by making the tree with the greater root the first child of the other.
+    fn link(&mut self, tree1: Self::Tree, tree2: Self::Tree) -> Self::Tree;
+
+    /// Called at the beginning of each pass over a list, e.g. to count the passes.
+    /// Does nothing by default.
+    fn start_pass(&mut self) {}
+}
+
+/// `PairingStrategy` determines the order in which [`PairingHeap`](crate::PairingHeap)
+/// links the children of the popped root.
+pub trait PairingStrategy {
+    /// If `true`, `push` puts new elements into an auxiliary list instead of linking them to the main tree.
+    /// The auxiliary list is combined by multipass pairing and linked to the main tree before `pop` and `meld`.
+    const AUXILIARY: bool = false;
+
+    /// Links the list of trees starting at `first` into a single tree.
+    fn link_all<L: SiblingList>(list: &mut L, first: L::Tree) -> L::Tree;
+}
+
+/// The standard two-pass strategy: link pairs from front to back, then link the results from back to front.
+pub struct TwoPass;
+
+impl PairingStrategy for TwoPass {
+    fn link_all<L: SiblingList>(list: &mut L, first: L::Tree) -> L::Tree {
+        let stack = pair_and_reverse(list, first);
+        link_front_to_back(list, stack)
+    }
+}
+
+/// The multipass strategy: link pairs from front to back repeatedly until one tree remains.
+pub struct Multipass;
+
+impl PairingStrategy for Multipass {
+    fn link_all<L: SiblingList>(list: &mut L, first: L::Tree) -> L::Tree {
+        let mut head = first;
+        loop {
+            head = pair_and_reverse(list, head);
+            let Some(next) = list.take_next(&mut head) else {
+                return head;
+            };
+            list.set_next(&mut head, Some(next));
+        }
+    }
+}
+
+/// The front-to-back strategy: link each tree to the result of the preceding trees.
+///
+/// This strategy does not guarantee O(log n) amortized `pop`, and is much slower than [`TwoPass`] on random inputs.
+pub struct FrontToBack;
+
+impl PairingStrategy for FrontToBack {
+    fn link_all<L: SiblingList>(list: &mut L, first: L::Tree) -> L::Tree {
+        link_front_to_back(list, first)
+    }
+}
+
+/// The back-to-front strategy: link each tree to the result of the following trees.
+///
+/// This strategy does not guarantee O(log n) amortized `pop`, and is much slower than [`TwoPass`] on random inputs.
+pub struct BackToFront;
+
+impl PairingStrategy for BackToFront {
+    fn link_all<L: SiblingList>(list: &mut L, first: L::Tree) -> L::Tree {
+        let stack = reverse(list, first);
+        link_front_to_back(list, stack)
+    }
+}
+
+/// The auxiliary two-pass strategy: `push` takes O(1) time without touching the main tree.
+/// The children of the popped root are linked by the two-pass strategy.
+///
+/// # Reference
+/// Stasko, John T.; Vitter, Jeffrey Scott (1987).
+/// ["Pairing Heaps: Experiments and Analysis"][Stasko-Vitter-1987]
+///
+/// [Stasko-Vitter-1987]: https://doi.org/10.1145/214748.214759
+pub struct AuxiliaryTwoPass;
+
+impl PairingStrategy for AuxiliaryTwoPass {
+    const AUXILIARY: bool = true;
+
+    fn link_all<L: SiblingList>(list: &mut L, first: L::Tree) -> L::Tree {
+        TwoPass::link_all(list, first)
+    }
+}
+
+// Link pairs from front to back, and return the results in reverse order.
+fn pair_and_reverse<L: SiblingList>(list: &mut L, first: L::Tree) -> L::Tree {
+    list.start_pass();
+    let mut it = Some(first);
+    let mut stack = None;
+    // For each iteration, take 2 trees from `it` and link them.
+    while let Some(mut tree1) = it {
+        it = list.take_next(&mut tree1);
+        let mut melt = if let Some(mut tree2) = it {
+            it = list.take_next(&mut tree2);
+            list.link(tree1, tree2)
+        } else {
+            tree1
+        };
+        // Push `melt` to `stack`.
+        list.set_next(&mut melt, stack);
+        stack = Some(melt);
+    }
+    stack.unwrap()
+}
+
+fn reverse<L: SiblingList>(list: &mut L, first: L::Tree) -> L::Tree {
+    list.start_pass();
+    let mut it = Some(first);
+    let mut stack = None;
+    while let Some(mut tree) = it {
+        it = list.take_next(&mut tree);
+        list.set_next(&mut tree, stack);
+        stack = Some(tree);
+    }
+    stack.unwrap()
+}
+
+fn link_front_to_back<L: SiblingList>(list: &mut L, first: L::Tree) -> L::Tree {
+    list.start_pass();
+    let mut ret = first;
+    let mut it = list.take_next(&mut ret);
+    while let Some(mut tree) = it {
+        it = list.take_next(&mut tree);
+        ret = list.link(tree, ret);
+    }
+    ret
+}
@@ -0,0 +1,348 @@
+#![forbid(unsafe_code)]
+
+use core::fmt;
+
+use alloc::vec::Vec;
+
+use crate::MeldableHeap;
+
+/// `RadixHeap` is a monotone priority queue of unsigned integer keys implemented with radix heap.
+/// `RadixHeap` is a **min-heap**, which means that the entry with the minimum key is popped first.
+///
+/// Keys must not be less than the last popped key, as in Dijkstra's algorithm.
+/// `push` returns [`MonotonicityError`] if the key violates this rule.
+///
+/// An entry is stored in the bucket determined by the highest bit in which its key differs from the last popped key.
+/// Each entry moves to a lower bucket whenever it is touched, so that `pop` takes O(C) amortized time,
+/// where C is the number of bits of `K`, regardless of the number of entries.
+///
+/// `RadixHeap<K, V>` implements [`MeldableHeap<(K, V)>`](MeldableHeap), whose `push` panics on a violation.
+///
+/// # Reference
+/// Ahuja, Ravindra K.; Mehlhorn, Kurt; Orlin, James B.; Tarjan, Robert E. (1990).
+/// ["Faster Algorithms for the Shortest Path Problem"][Ahuja-Mehlhorn-Orlin-Tarjan-1990]
+///
+/// [Ahuja-Mehlhorn-Orlin-Tarjan-1990]: https://doi.org/10.1145/77600.77615
+pub struct RadixHeap<K: RadixKey, V> {
+    // `buckets[0]` holds the entries whose key is equal to `last`,
+    // and `buckets[i]` holds the entries whose key differs from `last` at bit i - 1 and above.
+    buckets: Vec<Vec<(K, V)>>,
+    // The index of the entry with the minimum key in each bucket
+    bucket_min: Vec<usize>,
+    // The first non-empty bucket
+    min_bucket: Option<usize>,
+    last: K,
+    len: usize,
+}
+
+impl<K: RadixKey, V> RadixHeap<K, V> {
+    /// Constructs a empty `RadixHeap`.
+    /// O(C) time.
+    pub fn new() -> Self {
+        let buckets = (0..=K::BITS).map(|_| Vec::new()).collect();
+        Self {
+            buckets,
+            bucket_min: alloc::vec![0; K::BITS as usize + 1],
+            min_bucket: None,
+            last: K::MIN,
+            len: 0,
+        }
+    }
+
+    /// Returns `true` if the heap contains no elements.
+    /// O(1) time.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of elements in the heap.
+    /// O(1) time.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the last popped key, which is the lower bound of the keys that can be pushed.
+    /// O(1) time.
+    pub fn last(&self) -> K {
+        self.last
+    }
+
+    /// Inserts an entry into the heap.
+    /// O(1) time.
+    ///
+    /// # Errors
+    /// Returns [`MonotonicityError`] with the entry if `key` is less than the last popped key.
+    pub fn push(&mut self, key: K, value: V) -> Result<(), MonotonicityError<K, V>> {
+        if key < self.last {
+            return Err(MonotonicityError {
+                key,
+                value,
+                last: self.last,
+            });
+        }
+        let bucket = key.radix_distance(self.last) as usize;
+        self.push_to_bucket(bucket, (key, value));
+        if self.min_bucket.is_none_or(|min| bucket < min) {
+            self.min_bucket = Some(bucket);
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Removes the entry with the minimum key from the heap and returns it, or `None` if it is empty.
+    /// O(C) amortized time.
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        let i = self.min_bucket?;
+        if i != 0 {
+            // Redistribute the entries of the bucket relative to their minimum key.
+            let mut bucket = core::mem::take(&mut self.buckets[i]);
+            self.last = bucket[self.bucket_min[i]].0;
+            for entry in bucket.drain(..) {
+                let j = entry.0.radix_distance(self.last) as usize;
+                self.push_to_bucket(j, entry);
+            }
+            // Reuse the allocation of the bucket.
+            self.buckets[i] = bucket;
+        }
+
+        let entry = self.buckets[0].pop();
+        self.len -= 1;
+        self.min_bucket = self.buckets.iter().position(|bucket| !bucket.is_empty());
+        entry
+    }
+
+    /// Returns a reference to the entry with the minimum key in the heap, or `None` if it is empty.
+    /// O(1) time.
+    pub fn peek(&self) -> Option<&(K, V)> {
+        let i = self.min_bucket?;
+        if i == 0 {
+            self.buckets[0].last()
+        } else {
+            Some(&self.buckets[i][self.bucket_min[i]])
+        }
+    }
+
+    /// Melds two heaps into a single heap.
+    /// The last popped key of the result is the smaller one of the two heaps.
+    ///
+    /// This is not a true meld: the entries of one heap are pushed into the other one by one.
+    /// O(n) time. More precisely, O(m) time, where m is the number of entries in the heap
+    /// with the greater last popped key (or the smaller heap if they are equal).
+    pub fn meld(mut heap1: RadixHeap<K, V>, mut heap2: RadixHeap<K, V>) -> RadixHeap<K, V> {
+        if (heap1.last, heap2.len) > (heap2.last, heap1.len) {
+            core::mem::swap(&mut heap1, &mut heap2);
+        }
+        for bucket in heap2.buckets {
+            for (key, value) in bucket {
+                // `key` is not less than `heap2.last`, which is not less than `heap1.last`.
+                heap1
+                    .push(key, value)
+                    .unwrap_or_else(|_| unreachable!("meld: key is less than the last popped key"));
+            }
+        }
+        heap1
+    }
+
+    /// Returns an iterator that visits all entries in the heap, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = &(K, V)> {
+        self.buckets.iter().flatten()
+    }
+
+    fn push_to_bucket(&mut self, i: usize, entry: (K, V)) {
+        let bucket = &mut self.buckets[i];
+        if bucket.is_empty() || entry.0 < bucket[self.bucket_min[i]].0 {
+            self.bucket_min[i] = bucket.len();
+        }
+        bucket.push(entry);
+    }
+}
+
+impl<K: RadixKey, V> Default for RadixHeap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// `push` panics on a violation of monotonicity, because `MeldableHeap::push` cannot return an error.
+#[rustfmt::skip]
+impl<K: RadixKey, V> MeldableHeap<(K, V)> for RadixHeap<K, V> {
+    fn is_empty(&self) -> bool { self.is_empty() }
+    fn push(&mut self, (key, value): (K, V)) {
+        if self.push(key, value).is_err() { panic!("push: key is less than the last popped key") }
+    }
+    fn pop(&mut self) -> Option<(K, V)> { self.pop() }
+    fn peek(&self) -> Option<&(K, V)> { self.peek() }
+    fn meld(heap1: Self, heap2: Self) -> Self { Self::meld(heap1, heap2) }
+}
+
+/// The error returned by [`RadixHeap::push`] when the key is less than the last popped key.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MonotonicityError<K, V> {
+    /// The key of the rejected entry.
+    pub key: K,
+    /// The value of the rejected entry.
+    pub value: V,
+    /// The last popped key at the time of the push.
+    pub last: K,
+}
+
+impl<K, V> fmt::Display for MonotonicityError<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("key is less than the last popped key")
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> core::error::Error for MonotonicityError<K, V> {}
+
+/// `RadixKey` is an unsigned integer type which can be used as a key of [`RadixHeap`].
+pub trait RadixKey: Copy + Ord {
+    /// The minimum value of the type.
+    const MIN: Self;
+
+    /// The number of bits of the type.
+    const BITS: u32;
+
+    /// Returns one plus the position of the highest bit in which `self` and `other` differ,
+    /// or 0 if they are equal.
+    fn radix_distance(self, other: Self) -> u32;
+}
+
+macro_rules! impl_radix_key {
+    ($($t:ty),*) => {
+        $(
+            impl RadixKey for $t {
+                const MIN: Self = <$t>::MIN;
+                const BITS: u32 = <$t>::BITS;
+
+                fn radix_distance(self, other: Self) -> u32 {
+                    <$t>::BITS - (self ^ other).leading_zeros()
+                }
+            }
+        )*
+    };
+}
+
+impl_radix_key!(u8, u16, u32, u64, u128, usize);
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BinaryHeap;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use core::cmp::Reverse;
+
+    use crate::testing::{self, Inspect};
+    use crate::{MeldableHeap, MonotonicityError, RadixHeap};
+
+    // Keys without values, so that the checks of `testing` can be run.
+    // `check_model` is not applicable, since it pushes keys less than the last popped key.
+    #[derive(Default)]
+    struct Keys(RadixHeap<u32, ()>);
+
+    #[rustfmt::skip]
+    impl MeldableHeap<u32> for Keys {
+        fn is_empty(&self) -> bool { self.0.is_empty() }
+        fn push(&mut self, key: u32) { MeldableHeap::push(&mut self.0, (key, ())) }
+        fn pop(&mut self) -> Option<u32> { self.0.pop().map(|(key, _)| key) }
+        fn peek(&self) -> Option<&u32> { self.0.peek().map(|(key, _)| key) }
+        fn meld(heap1: Self, heap2: Self) -> Self { Self(RadixHeap::meld(heap1.0, heap2.0)) }
+    }
+
+    impl Inspect<u32> for Keys {
+        fn len(&self) -> Option<usize> {
+            Some(self.0.len())
+        }
+
+        fn elements(&self) -> Vec<&u32> {
+            self.0.iter().map(|(key, _)| key).collect()
+        }
+    }
+
+    #[test]
+    fn basic_test() {
+        testing::check_basic::<Keys>();
+    }
+
+    #[test]
+    fn large_drop_test() {
+        testing::check_deep::<Keys>(100000);
+    }
+
+    #[test]
+    fn monotonicity_test() {
+        let mut heap = RadixHeap::new();
+        heap.push(10u8, 'a').unwrap();
+        heap.push(20, 'b').unwrap();
+        assert_eq!(heap.pop(), Some((10, 'a')));
+        assert_eq!(heap.last(), 10);
+
+        // Keys between the last popped key and the minimum are accepted.
+        heap.push(10, 'c').unwrap();
+        heap.push(15, 'd').unwrap();
+        let err = heap.push(9, 'e').unwrap_err();
+        assert_eq!(
+            err,
+            MonotonicityError {
+                key: 9,
+                value: 'e',
+                last: 10
+            }
+        );
+        heap.push(u8::MAX, 'f').unwrap();
+
+        let actual: Vec<_> = core::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(actual, vec![(10, 'c'), (15, 'd'), (20, 'b'), (255, 'f')]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn meldable_heap_push_test() {
+        let mut heap = RadixHeap::new();
+        MeldableHeap::push(&mut heap, (10u64, ()));
+        heap.pop();
+        MeldableHeap::push(&mut heap, (9u64, ()));
+    }
+
+    #[test]
+    fn meld_test() {
+        let mut heap1 = RadixHeap::new();
+        let mut heap2 = RadixHeap::new();
+        for i in 0..100u64 {
+            heap1.push(2 * i, ()).unwrap();
+            heap2.push(2 * i + 1, ()).unwrap();
+        }
+        heap1.pop();
+        heap1.pop();
+        let mut heap = RadixHeap::meld(heap2, heap1);
+        assert_eq!(heap.last(), 0);
+        assert_eq!(heap.len(), 198);
+        let actual: Vec<_> = core::iter::from_fn(|| heap.pop()).map(|(k, _)| k).collect();
+        assert_eq!(actual, (1..200).filter(|&k| k != 2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn randomized_test() {
+        for _ in 0..1000 {
+            let mut heap = RadixHeap::<u32, ()>::new();
+            // BinaryHeap is max-heap. So, we need to push Reverse(x) to make it min-heap.
+            let mut expected = BinaryHeap::new();
+            for _ in 0..100 {
+                match rand::random::<u32>() % 2 {
+                    0 => {
+                        let x = heap.last() + rand::random::<u32>() % 100;
+                        heap.push(x, ()).unwrap();
+                        expected.push(Reverse(x));
+                    }
+                    1 => {
+                        let actual_x = heap.pop().map(|(k, _)| k);
+                        let expected_x = expected.pop().map(|r| r.0);
+                        assert_eq!(actual_x, expected_x);
+                    }
+                    _ => unreachable!(),
+                }
+                assert_eq!(expected.len(), heap.len());
+                assert_eq!(expected.peek().map(|r| r.0), heap.peek().map(|&(k, _)| k));
+            }
+        }
+    }
+}
@@ -0,0 +1,40 @@
+#![forbid(unsafe_code)]
+
+/// `RandomSource` is a seedable source of random bits used by randomized heaps.
+///
+/// Implement this trait to plug another pseudo-random number generator into a heap.
+pub trait RandomSource {
+    /// Constructs a generator from a seed.
+    /// The same seed must produce the same sequence of random bits.
+    fn seed_from_u64(seed: u64) -> Self;
+
+    /// Returns the next 64 random bits.
+    fn next_u64(&mut self) -> u64;
+}
+
+/// `SplitMix64` is a small and fast pseudo-random number generator.
+/// It is not cryptographically secure.
+///
+/// # Reference
+/// Steele, Guy L.; Lea, Doug; Flood, Christine H. (2014).
+/// ["Fast Splittable Pseudorandom Number Generators"][Steele-Lea-Flood-2014]
+///
+/// [Steele-Lea-Flood-2014]: https://doi.org/10.1145/2714064.2660195
+#[derive(Clone, Debug)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl RandomSource for SplitMix64 {
+    fn seed_from_u64(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+}
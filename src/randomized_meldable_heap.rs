@@ -0,0 +1,253 @@
+#![forbid(unsafe_code)]
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use crate::{MeldableHeap, RandomSource, SplitMix64};
+
+const DEFAULT_SEED: u64 = 0x5eed_5eed_5eed_5eed;
+
+/// `RandomizedMeldableHeap` is a priority queue implemented with randomized meldable heap.
+/// `RandomizedMeldableHeap` is a **min-heap**, which means that the minimum element is popped first.
+///
+/// `meld` walks down a random path of the tree by flipping a coin at each node,
+/// so nodes have no balance metadata. The coin is flipped by the random source `R`.
+/// A heap constructed by `new` or `default` uses a fixed seed, so its behavior is deterministic.
+///
+/// # Reference
+/// Gambin, Anna; Malinowski, Adam (1998).
+/// ["Randomized Meldable Priority Queues"][Gambin-Malinowski-1998]
+///
+/// [Gambin-Malinowski-1998]: https://doi.org/10.1007/3-540-49477-4_26
+pub struct RandomizedMeldableHeap<T: Ord, R: RandomSource = SplitMix64> {
+    root: Option<Box<Node<T>>>,
+    rng: R,
+}
+
+impl<T: Ord> RandomizedMeldableHeap<T> {
+    /// Constructs a empty `RandomizedMeldableHeap` with the default random source.
+    /// O(1) time.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<T: Ord, R: RandomSource> RandomizedMeldableHeap<T, R> {
+    /// Constructs a empty `RandomizedMeldableHeap` which flips coins with `rng`.
+    /// O(1) time.
+    pub fn with_rng(rng: R) -> Self {
+        Self { root: None, rng }
+    }
+
+    /// Returns `true` if the heap contains no elements.
+    /// O(1) time.
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Inserts a value into the heap.
+    /// O(log n) expected time.
+    pub fn push(&mut self, value: T) {
+        self.root = Node::meld(self.root.take(), Node::singleton(value), &mut self.rng);
+    }
+
+    /// Removes the minimum element from the heap and returns it, or `None` if it is empty.
+    /// O(log n) expected time.
+    pub fn pop(&mut self) -> Option<T> {
+        let root = self.root.take()?;
+        self.root = Node::meld(root.left, root.right, &mut self.rng);
+        Some(root.value)
+    }
+
+    /// Returns a reference to the minimum element in the heap, or `None` if it is empty.
+    /// O(1) time.
+    pub fn peek(&self) -> Option<&T> {
+        self.root.as_ref().map(|node| &node.value)
+    }
+
+    /// Melds two heaps into a single heap.
+    /// The random source of `heap1` is used for the result.
+    /// O(log n) expected time.
+    pub fn meld(mut heap1: Self, mut heap2: Self) -> Self {
+        heap1.root = Node::meld(heap1.root.take(), heap2.root.take(), &mut heap1.rng);
+        heap1
+    }
+
+    /// Returns an iterator that visits all elements in the heap, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let mut stack = Vec::new();
+        if let Some(ref root) = self.root {
+            stack.push(root.as_ref());
+        }
+        Iter { stack }
+    }
+}
+
+impl<T: Ord, R: RandomSource> Default for RandomizedMeldableHeap<T, R> {
+    fn default() -> Self {
+        Self::with_rng(R::seed_from_u64(DEFAULT_SEED))
+    }
+}
+
+#[rustfmt::skip]
+impl<T: Ord, R: RandomSource> MeldableHeap<T> for RandomizedMeldableHeap<T, R> {
+    fn is_empty(&self) -> bool { self.is_empty() }
+    fn push(&mut self, value: T) { self.push(value) }
+    fn pop(&mut self) -> Option<T> { self.pop() }
+    fn peek(&self) -> Option<&T> { self.peek() }
+    fn meld(heap1: Self, heap2: Self) -> Self { Self::meld(heap1, heap2) }
+}
+
+// We need to implement `drop` for RandomizedMeldableHeap because auto-generated `drop` would cause stack overflow
+// (the depth of the tree can be O(n) in the worst case).
+impl<T: Ord, R: RandomSource> Drop for RandomizedMeldableHeap<T, R> {
+    // Visit all nodes in depth-first order, and drop them one-by-one.
+    //
+    // This implementation reuses heap nodes to create a stack structure.
+    // Therefore, it consumes only O(1) memory except for the heap itself.
+    fn drop(&mut self) {
+        let mut stack_top = None;
+        let mut opt_node = self.root.take();
+
+        loop {
+            while let Some(mut node) = opt_node {
+                let left = node.left;
+
+                // push node to the stack
+                node.left = stack_top;
+                stack_top = Some(node);
+
+                // move to the left child
+                opt_node = left;
+            }
+
+            // pop a node from the stack
+            let Some(top) = stack_top else { break };
+            stack_top = top.left;
+            opt_node = top.right;
+
+            // `top` is deallocated here
+        }
+    }
+}
+
+struct Node<T: Ord> {
+    value: T,
+    left: Option<Box<Node<T>>>,
+    right: Option<Box<Node<T>>>,
+}
+
+impl<T: Ord> Node<T> {
+    fn singleton(value: T) -> Option<Box<Node<T>>> {
+        Some(Box::new(Self {
+            value,
+            left: None,
+            right: None,
+        }))
+    }
+
+    fn meld<R: RandomSource>(
+        root1: Option<Box<Node<T>>>,
+        root2: Option<Box<Node<T>>>,
+        rng: &mut R,
+    ) -> Option<Box<Node<T>>> {
+        let (mut root1, mut root2) = match (root1, root2) {
+            (None, root2) => return root2,
+            (root1, None) => return root1,
+            (Some(r1), Some(r2)) => (r1, r2),
+        };
+
+        // Ensure root1 <= root2
+        if root1.value > root2.value {
+            core::mem::swap(&mut root1, &mut root2);
+        }
+
+        // Setup loop variables
+        let mut parent = &mut root1;
+        let mut node2 = root2;
+
+        loop {
+            // Flip a coin to choose the subtree which `node2` is melded into
+            let child = if rng.next_u64() & 1 == 0 {
+                &mut parent.left
+            } else {
+                &mut parent.right
+            };
+
+            let Some(mut node1) = child.take() else {
+                *child = Some(node2);
+                break;
+            };
+
+            // Ensure node1 <= node2
+            if node1.value > node2.value {
+                core::mem::swap(&mut node1, &mut node2);
+            }
+
+            // Update loop variables
+            parent = child.insert(node1);
+        }
+
+        Some(root1)
+    }
+}
+
+struct Iter<'a, T: Ord> {
+    stack: Vec<&'a Node<T>>,
+}
+
+impl<'a, T: Ord> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        if let Some(left) = &node.left {
+            self.stack.push(left);
+        }
+        if let Some(right) = &node.right {
+            self.stack.push(right);
+        }
+        Some(&node.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::testing::{self, ModelConfig};
+    use crate::{RandomSource, RandomizedMeldableHeap, SplitMix64};
+
+    #[test]
+    fn basic_test() {
+        testing::check_basic::<RandomizedMeldableHeap<u32>>();
+    }
+
+    #[test]
+    fn large_drop_test() {
+        testing::check_deep::<RandomizedMeldableHeap<u32>>(1000000);
+    }
+
+    #[test]
+    fn randomized_test() {
+        let config = ModelConfig {
+            seed: rand::random(),
+            ..ModelConfig::default()
+        };
+        testing::check_model::<RandomizedMeldableHeap<u32>>(&config);
+    }
+
+    #[test]
+    fn deterministic_test() {
+        let build = |seed| {
+            let mut heap = RandomizedMeldableHeap::with_rng(SplitMix64::seed_from_u64(seed));
+            for x in [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5, 8, 9, 7, 9] {
+                heap.push(x);
+            }
+            heap.iter().copied().collect::<Vec<_>>()
+        };
+        // The shape of the tree, and hence the order of `iter`, is determined by the coin flips.
+        assert_eq!(build(42), [1, 3, 5, 9, 7, 3, 8, 9, 4, 9, 1, 2, 5, 5, 6]);
+        assert_ne!(build(43), build(42));
+    }
+}
@@ -0,0 +1,436 @@
+#![forbid(unsafe_code)]
+
+use core::cmp::max;
+use core::marker::PhantomData;
+
+use alloc::vec::Vec;
+
+use crate::{AddressableHeap, Handle, MeldableHeap};
+
+/// `RankPairingHeap` is a priority queue implemented with rank-pairing heap.
+/// `RankPairingHeap` is a **min-heap**, which means that the minimum element is popped first.
+///
+/// The rank rule is selected by the type parameter `R` ([`Type1Rank`] or [`Type2Rank`]).
+/// Use `RankPairingHeap::<T, Type1Rank>::default()` to construct a heap with the type-1 rank rule.
+///
+/// Nodes are stored in a vector and linked by indices, so that elements can be addressed by [`Handle`]s.
+///
+/// # Reference
+/// Haeupler, Bernhard; Sen, Siddhartha; Tarjan, Robert E. (2011).
+/// ["Rank-Pairing Heaps"][Haeupler-Sen-Tarjan-2011]
+///
+/// [Haeupler-Sen-Tarjan-2011]: https://sidsen.azurewebsites.net/papers/rp-heaps-journal.pdf
+pub struct RankPairingHeap<T: Ord, R: RankRule = Type2Rank> {
+    nodes: Vec<Node<T>>,
+    free: Vec<usize>,
+    roots: Vec<usize>,
+    min: Option<usize>,
+    len: usize,
+    rule: PhantomData<R>,
+}
+
+impl<T: Ord> RankPairingHeap<T> {
+    /// Constructs a empty `RankPairingHeap` with the type-2 rank rule.
+    /// O(1) time.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<T: Ord, R: RankRule> RankPairingHeap<T, R> {
+    /// Returns `true` if the heap contains no elements.
+    /// O(1) time.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of elements in the heap.
+    /// O(1) time.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Inserts a value into the heap and returns a handle to it.
+    /// O(1) time.
+    pub fn push(&mut self, value: T) -> Handle {
+        let index = self.alloc(value);
+        self.add_root(index);
+        self.len += 1;
+        Handle(index)
+    }
+
+    /// Removes the minimum element from the heap and returns it, or `None` if it is empty.
+    /// O(log n) amortized time.
+    pub fn pop(&mut self) -> Option<T> {
+        let min = self.min?;
+        Some(self.remove_root(min))
+    }
+
+    /// Returns a reference to the minimum element in the heap, or `None` if it is empty.
+    /// O(1) time.
+    pub fn peek(&self) -> Option<&T> {
+        self.min.map(|index| self.value(index))
+    }
+
+    /// Melds two heaps into a single heap.
+    /// O(m) time, where m is the number of nodes in `heap2`.
+    ///
+    /// The nodes of `heap2` are moved into the storage of `heap1`.
+    /// Handles to the elements of `heap1` remain valid, but handles to the elements of `heap2` are invalidated.
+    pub fn meld(mut heap1: Self, heap2: Self) -> Self {
+        let offset = heap1.nodes.len();
+        let shift = |index: Option<usize>| index.map(|i| i + offset);
+
+        heap1.nodes.extend(heap2.nodes.into_iter().map(|node| Node {
+            value: node.value,
+            rank: node.rank,
+            parent: shift(node.parent),
+            left: shift(node.left),
+            right: shift(node.right),
+        }));
+        heap1
+            .free
+            .extend(heap2.free.into_iter().map(|i| i + offset));
+        heap1
+            .roots
+            .extend(heap2.roots.into_iter().map(|i| i + offset));
+        heap1.len += heap2.len;
+        if let Some(min2) = shift(heap2.min) {
+            heap1.update_min(min2);
+        }
+        heap1
+    }
+
+    /// Returns a reference to the element of `handle`, or `None` if it has been removed.
+    /// O(1) time.
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.nodes.get(handle.0)?.value.as_ref()
+    }
+
+    /// Replaces the element of `handle` with `value`.
+    /// O(1) amortized time.
+    ///
+    /// # Panics
+    /// Panics if the element has been removed or `value` is greater than the current element.
+    pub fn decrease_key(&mut self, handle: Handle, value: T) {
+        let index = handle.0;
+        let current = self
+            .nodes
+            .get_mut(index)
+            .and_then(|node| node.value.as_mut())
+            .expect("decrease_key: invalid handle");
+        assert!(
+            value <= *current,
+            "decrease_key: new value is greater than the current value"
+        );
+        *current = value;
+
+        if self.nodes[index].parent.is_some() {
+            self.cut(index);
+            self.roots.push(index);
+        }
+        self.update_min(index);
+    }
+
+    /// Removes the element of `handle` from the heap and returns it, or `None` if it has been removed.
+    /// O(log n) amortized time.
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        let index = handle.0;
+        self.nodes.get(index)?.value.as_ref()?;
+        if self.nodes[index].parent.is_some() {
+            self.cut(index);
+            self.roots.push(index);
+        }
+        Some(self.remove_root(index))
+    }
+
+    /// Returns an iterator that visits all elements in the heap, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.nodes.iter().filter_map(|node| node.value.as_ref())
+    }
+
+    fn value(&self, index: usize) -> &T {
+        self.nodes[index].value.as_ref().unwrap()
+    }
+
+    fn rank(&self, index: Option<usize>) -> Option<u8> {
+        index.map(|i| self.nodes[i].rank)
+    }
+
+    // The rank of a root is one greater than the rank of its left child.
+    fn root_rank(&self, index: usize) -> u8 {
+        self.rank(self.nodes[index].left).map_or(0, |rank| rank + 1)
+    }
+
+    fn alloc(&mut self, value: T) -> usize {
+        let node = Node {
+            value: Some(value),
+            rank: 0,
+            parent: None,
+            left: None,
+            right: None,
+        };
+        if let Some(index) = self.free.pop() {
+            self.nodes[index] = node;
+            index
+        } else {
+            self.nodes.push(node);
+            self.nodes.len() - 1
+        }
+    }
+
+    fn add_root(&mut self, index: usize) {
+        self.roots.push(index);
+        self.update_min(index);
+    }
+
+    fn update_min(&mut self, index: usize) {
+        match self.min {
+            Some(min) if self.value(min) <= self.value(index) => {}
+            _ => self.min = Some(index),
+        }
+    }
+
+    // Detach the half-tree rooted at `index` from its parent, and restore the rank rule of its ancestors.
+    // The caller is responsible for adding `index` to the root list.
+    fn cut(&mut self, index: usize) {
+        let parent = self.nodes[index].parent.take().unwrap();
+        let right = self.nodes[index].right.take();
+
+        // Replace `index` with its right child.
+        if self.nodes[parent].left == Some(index) {
+            self.nodes[parent].left = right;
+        } else {
+            self.nodes[parent].right = right;
+        }
+        if let Some(right) = right {
+            self.nodes[right].parent = Some(parent);
+        }
+        self.nodes[index].rank = self.root_rank(index);
+
+        // Walk up to the root, decreasing ranks until the rank rule holds.
+        let mut u = parent;
+        loop {
+            let Some(next) = self.nodes[u].parent else {
+                self.nodes[u].rank = self.root_rank(u);
+                break;
+            };
+            let rank = R::rank(
+                self.rank(self.nodes[u].left),
+                self.rank(self.nodes[u].right),
+            );
+            if rank >= self.nodes[u].rank {
+                break;
+            }
+            self.nodes[u].rank = rank;
+            u = next;
+        }
+    }
+
+    // Link two half-trees of the same rank and return the root of the new half-tree.
+    fn link(&mut self, mut x: usize, mut y: usize) -> usize {
+        // Ensure x <= y
+        if self.value(x) > self.value(y) {
+            core::mem::swap(&mut x, &mut y);
+        }
+
+        // Make `y` the left child of `x`, and the old left child of `x` the right child of `y`.
+        let left = self.nodes[x].left;
+        self.nodes[y].right = left;
+        if let Some(left) = left {
+            self.nodes[left].parent = Some(y);
+        }
+        self.nodes[y].parent = Some(x);
+        self.nodes[x].left = Some(y);
+        self.nodes[x].rank += 1;
+        x
+    }
+
+    // Remove the root `index` from the heap, and rebuild the root list by one-pass linking.
+    fn remove_root(&mut self, index: usize) -> T {
+        let mut buckets: Vec<Option<usize>> = Vec::new();
+        let mut new_roots = Vec::new();
+        let mut add = |heap: &mut Self, root: usize| {
+            let rank = heap.nodes[root].rank as usize;
+            if buckets.len() <= rank {
+                buckets.resize(rank + 1, None);
+            }
+            match buckets[rank].take() {
+                Some(other) => new_roots.push(heap.link(root, other)),
+                None => buckets[rank] = Some(root),
+            }
+        };
+
+        // Disassemble the right spine of the left child into half-trees.
+        let mut it = self.nodes[index].left.take();
+        while let Some(child) = it {
+            it = self.nodes[child].right.take();
+            self.nodes[child].parent = None;
+            self.nodes[child].rank = self.root_rank(child);
+            add(self, child);
+        }
+
+        for root in core::mem::take(&mut self.roots) {
+            if root != index {
+                add(self, root);
+            }
+        }
+
+        new_roots.extend(buckets.into_iter().flatten());
+        self.roots = new_roots;
+        self.min = None;
+        for i in 0..self.roots.len() {
+            self.update_min(self.roots[i]);
+        }
+
+        self.len -= 1;
+        self.free.push(index);
+        self.nodes[index].value.take().unwrap()
+    }
+}
+
+impl<T: Ord, R: RankRule> Default for RankPairingHeap<T, R> {
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            roots: Vec::new(),
+            min: None,
+            len: 0,
+            rule: PhantomData,
+        }
+    }
+}
+
+#[rustfmt::skip]
+impl<T: Ord, R: RankRule> MeldableHeap<T> for RankPairingHeap<T, R> {
+    fn is_empty(&self) -> bool { self.is_empty() }
+    fn push(&mut self, value: T) { self.push(value); }
+    fn pop(&mut self) -> Option<T> { self.pop() }
+    fn peek(&self) -> Option<&T> { self.peek() }
+    fn meld(heap1: Self, heap2: Self) -> Self { Self::meld(heap1, heap2) }
+}
+
+#[rustfmt::skip]
+impl<T: Ord, R: RankRule> AddressableHeap<T> for RankPairingHeap<T, R> {
+    fn push_with_handle(&mut self, value: T) -> Handle { self.push(value) }
+    fn get(&self, handle: Handle) -> Option<&T> { self.get(handle) }
+    fn decrease_key(&mut self, handle: Handle, value: T) { self.decrease_key(handle, value) }
+    fn remove(&mut self, handle: Handle) -> Option<T> { self.remove(handle) }
+}
+
+/// `RankRule` determines the rank of a non-root node of a [`RankPairingHeap`] from the ranks of its children.
+pub trait RankRule {
+    /// Returns the rank of a node whose children have the given ranks (`None` for a missing child).
+    fn rank(left: Option<u8>, right: Option<u8>) -> u8;
+}
+
+/// The type-1 rank rule: every non-root node is a 1,1-node or a 0,i-node.
+pub struct Type1Rank;
+
+impl RankRule for Type1Rank {
+    fn rank(left: Option<u8>, right: Option<u8>) -> u8 {
+        let r1 = left.map_or(-1, i16::from);
+        let r2 = right.map_or(-1, i16::from);
+        let rank = if r1 == r2 { r1 + 1 } else { max(r1, r2) };
+        rank as u8
+    }
+}
+
+/// The type-2 rank rule: every non-root node is a 1,1-node, a 1,2-node or a 0,i-node for i > 1.
+pub struct Type2Rank;
+
+impl RankRule for Type2Rank {
+    fn rank(left: Option<u8>, right: Option<u8>) -> u8 {
+        let r1 = left.map_or(-1, i16::from);
+        let r2 = right.map_or(-1, i16::from);
+        let rank = if (r1 - r2).abs() <= 1 {
+            max(r1, r2) + 1
+        } else {
+            max(r1, r2)
+        };
+        rank as u8
+    }
+}
+
+struct Node<T> {
+    // `None` if the node is in the free list
+    value: Option<T>,
+    rank: u8,
+    parent: Option<usize>,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use crate::testing::{self, ModelConfig};
+    use crate::{RankPairingHeap, Type1Rank, Type2Rank};
+
+    #[test]
+    fn basic_test() {
+        testing::check_basic::<RankPairingHeap<u32>>();
+    }
+
+    #[test]
+    fn large_drop_test() {
+        testing::check_deep::<RankPairingHeap<u32>>(100000);
+    }
+
+    #[test]
+    fn randomized_test() {
+        let config = ModelConfig {
+            seed: rand::random(),
+            ..ModelConfig::default()
+        };
+        testing::check_model::<RankPairingHeap<u32, Type1Rank>>(&config);
+        testing::check_model::<RankPairingHeap<u32, Type2Rank>>(&config);
+        testing::check_addressable::<RankPairingHeap<u32, Type1Rank>>(&config);
+        testing::check_addressable::<RankPairingHeap<u32, Type2Rank>>(&config);
+    }
+
+    #[test]
+    fn meld_test() {
+        let mut heap1 = RankPairingHeap::new();
+        let mut heap2 = RankPairingHeap::new();
+        let h1 = heap1.push(10);
+        heap1.push(30);
+        heap2.push(20);
+        heap2.push(5);
+        let mut heap = RankPairingHeap::meld(heap1, heap2);
+        heap.decrease_key(h1, 1);
+        assert_eq!(heap.len(), 4);
+        let actual: Vec<_> = core::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(vec![1, 5, 20, 30], actual);
+    }
+
+    #[test]
+    #[should_panic(expected = "decrease_key: invalid handle")]
+    fn invalid_handle_test() {
+        let mut heap1 = RankPairingHeap::new();
+        let mut heap2 = RankPairingHeap::new();
+        heap1.push(1);
+        heap2.push(2);
+        // A handle of `heap2` which is out of range in `heap1`
+        let handle = heap2.push(3);
+        heap1.decrease_key(handle, 0);
+    }
+
+    #[test]
+    fn decrease_key_test() {
+        let mut heap = RankPairingHeap::new();
+        let handles: Vec<_> = (0..100).map(|i| heap.push(1000 + i)).collect();
+        heap.pop();
+        for (i, &h) in handles.iter().enumerate().skip(1).rev() {
+            heap.decrease_key(h, i as i32);
+        }
+        assert_eq!(heap.remove(handles[50]), Some(50));
+        assert_eq!(heap.remove(handles[50]), None);
+        let actual: Vec<_> = core::iter::from_fn(|| heap.pop()).collect();
+        let expected: Vec<_> = (1..100).filter(|&i| i != 50).collect();
+        assert_eq!(expected, actual);
+    }
+}
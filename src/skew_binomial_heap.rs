@@ -0,0 +1,357 @@
+#![forbid(unsafe_code)]
+
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::MeldableHeap;
+
+/// `SkewBinomialHeap` is a priority queue implemented with skew binomial heap.
+/// `SkewBinomialHeap` is a **min-heap**, which means that the minimum element is popped first.
+///
+/// The minimum element is kept outside of the trees, so that `peek` takes O(1) time.
+///
+/// # Reference
+/// Brodal, Gerth Stølting; Okasaki, Chris (1996).
+/// ["Optimal Purely Functional Priority Queues"][Brodal-Okasaki-1996]
+///
+/// [Brodal-Okasaki-1996]: https://doi.org/10.1017/S095679680000201X
+pub struct SkewBinomialHeap<T: Ord> {
+    min: Option<T>,
+    // A list of trees in increasing order of rank, except that the first two trees may have the same rank.
+    trees: Option<Box<Node<T>>>,
+}
+
+impl<T: Ord> SkewBinomialHeap<T> {
+    /// Constructs a empty `SkewBinomialHeap`.
+    /// O(1) time.
+    pub fn new() -> Self {
+        Self {
+            min: None,
+            trees: None,
+        }
+    }
+
+    /// Returns `true` if the heap contains no elements.
+    /// O(1) time.
+    pub fn is_empty(&self) -> bool {
+        self.min.is_none()
+    }
+
+    /// Inserts a value into the heap.
+    /// O(1) time.
+    pub fn push(&mut self, mut value: T) {
+        let Some(min) = &mut self.min else {
+            self.min = Some(value);
+            return;
+        };
+        if value < *min {
+            core::mem::swap(&mut value, min);
+        }
+        Node::insert(&mut self.trees, Node::singleton(value));
+    }
+
+    /// Removes the minimum element from the heap and returns it, or `None` if it is empty.
+    /// O(log n) time.
+    pub fn pop(&mut self) -> Option<T> {
+        let min = self.min.take()?;
+        self.min = Node::delete_min(&mut self.trees);
+        Some(min)
+    }
+
+    /// Returns a reference to the minimum element in the heap, or `None` if it is empty.
+    /// O(1) time.
+    pub fn peek(&self) -> Option<&T> {
+        self.min.as_ref()
+    }
+
+    /// Melds two heaps into a single heap.
+    /// O(log n) time.
+    pub fn meld(
+        mut heap1: SkewBinomialHeap<T>,
+        mut heap2: SkewBinomialHeap<T>,
+    ) -> SkewBinomialHeap<T> {
+        if heap1.is_empty() {
+            return heap2;
+        }
+        if heap2.is_empty() {
+            return heap1;
+        }
+        let mut min1 = heap1.min.take().unwrap();
+        let mut min2 = heap2.min.take().unwrap();
+
+        // Ensure min1 <= min2
+        if min1 > min2 {
+            core::mem::swap(&mut min1, &mut min2);
+        }
+
+        let mut trees = Node::meld(heap1.trees.take(), heap2.trees.take());
+        Node::insert(&mut trees, Node::singleton(min2));
+        Self {
+            min: Some(min1),
+            trees,
+        }
+    }
+
+    /// Returns an iterator that visits all elements in the heap, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let mut stack = Vec::new();
+        if let Some(ref node) = self.trees {
+            stack.push(node.as_ref());
+        }
+        self.min.iter().chain(Iter { stack })
+    }
+}
+
+impl<T: Ord> Default for SkewBinomialHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[rustfmt::skip]
+impl<T: Ord> MeldableHeap<T> for SkewBinomialHeap<T> {
+    fn is_empty(&self) -> bool { self.is_empty() }
+    fn push(&mut self, value: T) { self.push(value) }
+    fn pop(&mut self) -> Option<T> { self.pop() }
+    fn peek(&self) -> Option<&T> { self.peek() }
+    fn meld(heap1: Self, heap2: Self) -> Self { Self::meld(heap1, heap2) }
+}
+
+// The depth of the trees is O(log n), but lists of siblings are linked by `next`,
+// so auto-generated `drop` would recurse once per sibling. We drop nodes one-by-one to be safe.
+impl<T: Ord> Drop for SkewBinomialHeap<T> {
+    // This implementation reuses heap nodes to create a stack structure.
+    // Therefore, it consumes only O(1) memory except for the heap itself.
+    fn drop(&mut self) {
+        let mut stack_top = self.trees.take();
+
+        while let Some(mut node) = stack_top {
+            stack_top = node.next.take();
+
+            // move children and auxiliary nodes to the stack
+            for mut list in [node.children.take(), node.aux.take()] {
+                while let Some(mut child) = list {
+                    list = child.next.take();
+                    child.next = stack_top;
+                    stack_top = Some(child);
+                }
+            }
+
+            // `node` is deallocated here
+        }
+    }
+}
+
+struct Node<T: Ord> {
+    value: T,
+    rank: u8,
+    // Children in decreasing order of rank
+    children: Option<Box<Node<T>>>,
+    // Rank-0 nodes attached by skew links
+    aux: Option<Box<Node<T>>>,
+    next: Option<Box<Node<T>>>,
+}
+
+impl<T: Ord> Node<T> {
+    fn singleton(value: T) -> Box<Node<T>> {
+        Box::new(Self {
+            value,
+            rank: 0,
+            children: None,
+            aux: None,
+            next: None,
+        })
+    }
+
+    // Link two trees of the same rank.
+    fn link(mut root1: Box<Node<T>>, mut root2: Box<Node<T>>) -> Box<Node<T>> {
+        // Ensure root1 <= root2
+        if root1.value > root2.value {
+            core::mem::swap(&mut root1, &mut root2);
+        }
+
+        // Connect root2 as the first child of root1
+        root2.next = root1.children.take();
+        root1.children = Some(root2);
+        root1.rank += 1;
+
+        root1
+    }
+
+    // Insert a singleton node into a list of trees.
+    // If the first two trees have the same rank, they are combined with the new node by a skew link.
+    fn insert(trees: &mut Option<Box<Node<T>>>, mut node: Box<Node<T>>) {
+        let skew =
+            matches!(trees, Some(t1) if t1.next.as_ref().is_some_and(|t2| t1.rank == t2.rank));
+        if !skew {
+            node.next = trees.take();
+            *trees = Some(node);
+            return;
+        }
+
+        let mut t1 = trees.take().unwrap();
+        let mut t2 = t1.next.take().unwrap();
+        let rest = t2.next.take();
+        let mut root = Self::link(t1, t2);
+
+        // The smaller of `node` and `root` becomes the root, and the other becomes an auxiliary node.
+        if node.value < root.value {
+            core::mem::swap(&mut node.value, &mut root.value);
+        }
+        node.next = root.aux.take();
+        root.aux = Some(node);
+
+        root.next = rest;
+        *trees = Some(root);
+    }
+
+    // Split a list of trees into a vector of trees with distinct ranks in increasing order.
+    fn normalize(trees: Option<Box<Node<T>>>) -> Vec<Box<Node<T>>> {
+        let mut vec = Vec::new();
+        let Some(mut first) = trees else { return vec };
+
+        // Only the first two trees may have the same rank. Link them and propagate carries.
+        let mut rest = first.next.take();
+        while rest.as_ref().is_some_and(|tree| tree.rank == first.rank) {
+            let mut tree = rest.unwrap();
+            rest = tree.next.take();
+            first = Self::link(first, tree);
+        }
+        vec.push(first);
+
+        while let Some(mut tree) = rest {
+            rest = tree.next.take();
+            vec.push(tree);
+        }
+        vec
+    }
+
+    fn meld(trees1: Option<Box<Node<T>>>, trees2: Option<Box<Node<T>>>) -> Option<Box<Node<T>>> {
+        let trees1 = Self::normalize(trees1);
+        let trees2 = Self::normalize(trees2);
+        let mut it1 = trees1.into_iter().peekable();
+        let mut it2 = trees2.into_iter().peekable();
+
+        // Add two lists of trees like binary numbers.
+        let mut merged = Vec::new();
+        let mut carry: Option<Box<Node<T>>> = None;
+        loop {
+            let rank = [carry.as_ref(), it1.peek(), it2.peek()]
+                .into_iter()
+                .flatten()
+                .map(|tree| tree.rank)
+                .min();
+            let Some(rank) = rank else { break };
+
+            let mut same_rank = Vec::with_capacity(3);
+            if carry.as_ref().is_some_and(|tree| tree.rank == rank) {
+                same_rank.extend(carry.take());
+            }
+            if it1.peek().is_some_and(|tree| tree.rank == rank) {
+                same_rank.extend(it1.next());
+            }
+            if it2.peek().is_some_and(|tree| tree.rank == rank) {
+                same_rank.extend(it2.next());
+            }
+
+            if same_rank.len() % 2 == 1 {
+                merged.push(same_rank.pop().unwrap());
+            }
+            if let (Some(t2), Some(t1)) = (same_rank.pop(), same_rank.pop()) {
+                carry = Some(Self::link(t1, t2));
+            }
+        }
+
+        let mut trees = None;
+        for mut tree in merged.into_iter().rev() {
+            tree.next = trees;
+            trees = Some(tree);
+        }
+        trees
+    }
+
+    // Remove the minimum root from a list of trees and return its value.
+    fn delete_min(trees: &mut Option<Box<Node<T>>>) -> Option<T> {
+        let mut vec = Vec::new();
+        let mut it = trees.take();
+        while let Some(mut tree) = it {
+            it = tree.next.take();
+            vec.push(tree);
+        }
+
+        let (index, _) = vec
+            .iter()
+            .enumerate()
+            .min_by(|(_, t1), (_, t2)| t1.value.cmp(&t2.value))?;
+        let mut min_tree = vec.remove(index);
+
+        let mut rest = None;
+        for mut tree in vec.into_iter().rev() {
+            tree.next = rest;
+            rest = Some(tree);
+        }
+
+        // Children are in decreasing order of rank, so reverse them.
+        let mut children = None;
+        let mut it = min_tree.children.take();
+        while let Some(mut child) = it {
+            it = child.next.take();
+            child.next = children;
+            children = Some(child);
+        }
+
+        *trees = Self::meld(rest, children);
+
+        // Reinsert auxiliary nodes.
+        let mut it = min_tree.aux.take();
+        while let Some(mut node) = it {
+            it = node.next.take();
+            Self::insert(trees, node);
+        }
+
+        Some(min_tree.value)
+    }
+}
+
+struct Iter<'a, T: Ord> {
+    stack: Vec<&'a Node<T>>,
+}
+
+impl<'a, T: Ord> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        for next in [&node.children, &node.aux, &node.next]
+            .into_iter()
+            .flatten()
+        {
+            self.stack.push(next);
+        }
+        Some(&node.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::{self, ModelConfig};
+    use crate::SkewBinomialHeap;
+
+    #[test]
+    fn basic_test() {
+        testing::check_basic::<SkewBinomialHeap<u32>>();
+    }
+
+    #[test]
+    fn large_drop_test() {
+        testing::check_deep::<SkewBinomialHeap<u32>>(1000000);
+    }
+
+    #[test]
+    fn randomized_test() {
+        let config = ModelConfig {
+            seed: rand::random(),
+            ..ModelConfig::default()
+        };
+        testing::check_model::<SkewBinomialHeap<u32>>(&config);
+    }
+}
@@ -1,8 +1,19 @@
 #![forbid(unsafe_code)]
 
+use core::cmp::Reverse;
+use core::fmt::Debug;
+
 use alloc::boxed::Box;
+use alloc::collections::BinaryHeap;
+use alloc::string::String;
 use alloc::vec::Vec;
 
+use crate::{
+    binary_tree::{self, BinaryNode},
+    AddAll, AdditiveKey, Aggregate, Counters, Entry, HeapStats, InvariantError, Lazy, LeftistHeap,
+    MeldableHeap, PairingHeap, PairingStrategy, StatsBuilder,
+};
+
 /// `SkewHeap` is a priority queue implemented with skew heaps.
 /// `SkewHeap` is a **min-heap**, which means that the minimum element is popped first.
 ///
@@ -11,17 +22,23 @@ use alloc::vec::Vec;
 /// ["Self-Adjusting Heaps"][Sleator-Tarjan-1986]
 ///
 /// [Sleator-Tarjan-1986]: https://www.cs.cmu.edu/~sleator/papers/Adjusting-Heaps.htm
-pub struct SkewHeap<T: Ord> {
-    root: Option<Box<Node<T>>>,
+///
+/// # Aggregates
+/// An [`Aggregate`] can be attached with the second type parameter, as in [`LeftistHeap`].
+pub struct SkewHeap<T: Ord, A: Aggregate<T> = ()> {
+    root: Option<Box<Node<T, A>>>,
+    counters: Counters,
 }
 
 impl<T: Ord> SkewHeap<T> {
     /// Constructs a empty `SkewHeap`.
     /// O(1) time.
     pub fn new() -> Self {
-        SkewHeap { root: None }
+        Self::default()
     }
+}
 
+impl<T: Ord, A: Aggregate<T>> SkewHeap<T, A> {
     /// Returns the number of elements in the heap.
     /// O(1) time.
     pub fn is_empty(&self) -> bool {
@@ -31,14 +48,17 @@ impl<T: Ord> SkewHeap<T> {
     /// Inserts a value into the heap.
     /// O(log n) amortized time.
     pub fn push(&mut self, value: T) {
-        self.root = Node::meld(self.root.take(), Node::singleton(value));
+        self.counters.allocation();
+        self.root = Node::meld(self.root.take(), Node::singleton(value), &mut self.counters);
+        self.debug_validate();
     }
 
     /// Removes the minimum element from the heap and returns it, or `None` if it is empty.
     /// O(log n) amortized time.
     pub fn pop(&mut self) -> Option<T> {
         let root = self.root.take()?;
-        self.root = Node::meld(root.left, root.right);
+        self.root = Node::meld(root.left, root.right, &mut self.counters);
+        self.debug_validate();
         Some(root.value)
     }
 
@@ -50,9 +70,13 @@ impl<T: Ord> SkewHeap<T> {
 
     /// Melds two heaps into a single heap.
     /// O(log n) amortized time.
-    pub fn meld(mut heap1: SkewHeap<T>, mut heap2: SkewHeap<T>) -> SkewHeap<T> {
-        let root = Node::meld(heap1.root.take(), heap2.root.take());
-        Self { root }
+    pub fn meld(mut heap1: SkewHeap<T, A>, mut heap2: SkewHeap<T, A>) -> SkewHeap<T, A> {
+        let mut counters = heap1.counters;
+        counters.absorb(&heap2.counters);
+        let root = Node::meld(heap1.root.take(), heap2.root.take(), &mut counters);
+        let heap = Self { root, counters };
+        heap.debug_validate();
+        heap
     }
 
     /// Returns an iterator that visits all elements in the heap, in arbitrary order.
@@ -63,39 +87,250 @@ impl<T: Ord> SkewHeap<T> {
         }
         Iter { stack }
     }
-}
 
-// We need to implement `drop` for SkewHeap because auto-generated `drop` would cause stack overflow
-// (the depth of the tree can be O(n) in the worst case).
-impl<T: Ord> Drop for SkewHeap<T> {
-    // Visit all nodes in depth-first order, and drop them one-by-one.
-    //
-    // This implementation reuses heap nodes to create a stack structure.
-    // Therefore, it consumes only O(1) memory except for the heap itself.
-    fn drop(&mut self) {
-        let mut stack_top = None;
-        let mut opt_node = self.root.take();
+    /// Returns an iterator that visits the elements not greater than `bound`, in arbitrary order.
+    /// Subtrees whose roots are greater than `bound` are skipped by the heap order.
+    /// O(k) time, where k is the number of elements not greater than `bound`.
+    pub fn iter_below<'a>(&'a self, bound: &'a T) -> impl Iterator<Item = &'a T> + 'a {
+        binary_tree::iter_below(self.root.as_deref(), bound)
+    }
 
-        loop {
-            while let Some(mut node) = opt_node {
-                let left = node.left;
+    /// Returns the number of elements not greater than `bound`.
+    /// O(k) time, where k is the number of elements not greater than `bound`.
+    pub fn count_below(&self, bound: &T) -> usize {
+        self.iter_below(bound).count()
+    }
 
-                // push node to the stack
-                node.left = stack_top;
-                stack_top = Some(node);
+    /// Removes the elements not greater than `bound` from the heap, and returns them as a new heap.
+    /// The removed nodes are detached by a depth-first search which stops at the nodes greater than `bound`,
+    /// and both heaps are rebuilt by melding the pieces in pairs.
+    /// O(k log n) amortized time, where k is the number of removed elements.
+    pub fn split_off_below(&mut self, bound: &T) -> Self {
+        let below = binary_tree::split_off_below(&mut self.root, bound, &mut self.counters);
+        self.debug_validate();
+        let heap = Self {
+            root: below,
+            counters: Counters::default(),
+        };
+        heap.debug_validate();
+        heap
+    }
 
-                // move to the left child
-                opt_node = left;
+    /// Rewrites all elements in place with `f`, keeping the shape of the tree.
+    /// `f` must preserve the order of the elements, i.e. `a <= b` must imply `f(a) <= f(b)`;
+    /// otherwise the heap order is broken. Use [`reprioritize`](Self::reprioritize) for other functions.
+    /// O(n) time.
+    pub fn map_monotone(&mut self, f: impl FnMut(&mut T)) {
+        binary_tree::map_monotone(&mut self.root, f);
+        self.debug_validate();
+    }
+
+    /// Rewrites all elements with `f`, and rebuilds the heap by melding the nodes in pairs, as `From<Vec<T>>` does.
+    /// Unlike [`map_monotone`](Self::map_monotone), `f` may change the order of the elements.
+    /// The nodes are reused, so no memory is allocated for them.
+    /// O(n) time.
+    pub fn reprioritize(&mut self, f: impl FnMut(&mut T)) {
+        binary_tree::reprioritize(&mut self.root, f, &mut self.counters);
+        self.debug_validate();
+    }
+
+    /// Returns the aggregate of all elements in the heap.
+    /// O(1) time.
+    pub fn aggregate(&self) -> A::Value {
+        self.root
+            .as_ref()
+            .map_or_else(A::identity, |node| node.agg.clone())
+    }
+
+    /// Returns the aggregate of the elements not greater than `bound`, as [`iter_below`](Self::iter_below) visits them.
+    /// The bound is inclusive, as in all bound queries, so the elements equal to `bound` are aggregated.
+    /// O(k) time, where k is the number of elements not greater than `bound`.
+    pub fn aggregate_below(&self, bound: &T) -> A::Value {
+        self.iter_below(bound).fold(A::identity(), |acc, value| {
+            A::combine(&acc, &A::lift(value))
+        })
+    }
+
+    /// Returns statistics of the shape of the heap.
+    /// O(n) time.
+    pub fn stats(&self) -> HeapStats {
+        let mut builder = StatsBuilder::default();
+        let mut stack = Vec::new();
+        if let Some(ref root) = self.root {
+            stack.push((root.as_ref(), 1));
+        }
+        while let Some((node, depth)) = stack.pop() {
+            let children = [&node.left, &node.right];
+            builder.node(
+                depth,
+                children.iter().filter(|child| child.is_some()).count(),
+            );
+            for child in children.into_iter().flatten() {
+                stack.push((child.as_ref(), depth + 1));
             }
+        }
+        let right_spine = core::iter::successors(self.cursor(), |cursor| cursor.right()).count();
+        builder.finish(right_spine, size_of::<Self>(), size_of::<Node<T, A>>())
+    }
 
-            // pop a node from the stack
-            let Some(top) = stack_top else { break };
-            stack_top = top.left;
-            opt_node = top.right;
+    /// Returns the operation counters of the heap.
+    /// O(1) time.
+    #[cfg(feature = "instrumentation")]
+    pub fn counters(&self) -> crate::OpCounters {
+        self.counters.get()
+    }
 
-            // `top` is deallocated here
+    /// Resets the operation counters of the heap to zero.
+    /// O(1) time.
+    #[cfg(feature = "instrumentation")]
+    pub fn reset_counters(&mut self) {
+        self.counters.reset();
+    }
+
+    /// Returns a cursor at the root, or `None` if the heap is empty.
+    /// O(1) time.
+    pub fn cursor(&self) -> Option<SkewHeapCursor<'_, T, A>> {
+        self.root.as_deref().map(|node| SkewHeapCursor { node })
+    }
+
+    /// Returns the tree in Graphviz DOT format.
+    /// Nodes are labeled with the `Debug` representation of their elements.
+    /// Edges to the left and right children are labeled `L` and `R`.
+    /// If `max_depth` is given, the children of the nodes at that depth (the root is at depth 0) are replaced with `...`.
+    /// O(n) time.
+    pub fn to_dot(&self, max_depth: Option<usize>) -> String
+    where
+        T: Debug,
+    {
+        crate::export::binary_to_dot(self.cursor(), max_depth)
+    }
+
+    /// Returns the tree in JSON, as `{"root": node or null}`.
+    /// Each node is `{"value": string, "left": node or null, "right": node or null}`,
+    /// where `value` is the `Debug` representation of the element.
+    /// If `max_depth` is given, the children of the nodes at that depth (the root is at depth 0)
+    /// are replaced with `{"truncated": true}`.
+    /// O(n) time.
+    pub fn to_json(&self, max_depth: Option<usize>) -> String
+    where
+        T: Debug,
+    {
+        crate::export::binary_to_json(self.cursor(), max_depth)
+    }
+
+    /// Checks the heap order of all nodes, and returns the first violation found.
+    /// O(n) time.
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        let mut stack = Vec::new();
+        if let Some(ref root) = self.root {
+            stack.push((root.as_ref(), 0));
+        }
+        while let Some((node, depth)) = stack.pop() {
+            for child in [&node.left, &node.right].into_iter().flatten() {
+                if child.value < node.value {
+                    return Err(InvariantError::HeapOrder { depth: depth + 1 });
+                }
+                stack.push((child.as_ref(), depth + 1));
+            }
+        }
+        Ok(())
+    }
+
+    // Validate the heap after a mutating operation if the `debug-invariants` feature is enabled.
+    fn debug_validate(&self) {
+        #[cfg(feature = "debug-invariants")]
+        if let Err(err) = self.validate() {
+            panic!("{err}");
+        }
+    }
+
+    // Move all elements out of the heap in arbitrary order, and pass them to `f` one-by-one.
+    pub(crate) fn drain_with(&mut self, f: impl FnMut(T)) {
+        Node::drain(self.root.take(), f);
+    }
+}
+
+impl<T: Ord, A: Aggregate<T>> Default for SkewHeap<T, A> {
+    fn default() -> Self {
+        Self {
+            root: None,
+            counters: Counters::default(),
         }
     }
+}
+
+#[rustfmt::skip]
+impl<T: Ord, A: Aggregate<T>> MeldableHeap<T> for SkewHeap<T, A> {
+    fn is_empty(&self) -> bool { self.is_empty() }
+    fn push(&mut self, value: T) { self.push(value) }
+    fn pop(&mut self) -> Option<T> { self.pop() }
+    fn peek(&self) -> Option<&T> { self.peek() }
+    fn meld(heap1: Self, heap2: Self) -> Self { Self::meld(heap1, heap2) }
+}
+
+impl<T: Ord> From<Vec<T>> for SkewHeap<T> {
+    /// Builds a heap from the elements of a vector.
+    /// O(n) time.
+    fn from(values: Vec<T>) -> Self {
+        let mut counters = Counters::default();
+        let roots = values
+            .into_iter()
+            .map(|value| {
+                counters.allocation();
+                Node::singleton(value)
+            })
+            .collect();
+        let heap = Self {
+            root: binary_tree::meld_all(roots, &mut counters),
+            counters,
+        };
+        heap.debug_validate();
+        heap
+    }
+}
+
+impl<T: Ord> From<BinaryHeap<Reverse<T>>> for SkewHeap<T> {
+    /// O(n) time.
+    fn from(heap: BinaryHeap<Reverse<T>>) -> Self {
+        Self::from(
+            heap.into_iter()
+                .map(|Reverse(value)| value)
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+impl<T: Ord, S: PairingStrategy> From<PairingHeap<T, S>> for SkewHeap<T> {
+    /// O(n) time.
+    fn from(heap: PairingHeap<T, S>) -> Self {
+        Self::from(Vec::from(heap))
+    }
+}
+
+impl<T: Ord, A: Aggregate<T>> From<LeftistHeap<T, A>> for SkewHeap<T> {
+    /// O(n) time.
+    fn from(heap: LeftistHeap<T, A>) -> Self {
+        Self::from(Vec::from(heap))
+    }
+}
+
+impl<T: Ord, A: Aggregate<T>> From<SkewHeap<T, A>> for Vec<T> {
+    /// Returns the elements of the heap in arbitrary order.
+    /// O(n) time.
+    fn from(mut heap: SkewHeap<T, A>) -> Self {
+        let mut values = Vec::new();
+        heap.drain_with(|value| values.push(value));
+        values
+    }
+}
+
+// We need to implement `drop` for SkewHeap because auto-generated `drop` would cause stack overflow
+// (the depth of the tree can be O(n) in the worst case).
+impl<T: Ord, A: Aggregate<T>> Drop for SkewHeap<T, A> {
+    // Visit all nodes in depth-first order, and drop them one-by-one.
+    fn drop(&mut self) {
+        self.drain_with(drop);
+    }
 
     /*
     // Naive implementation
@@ -115,36 +350,238 @@ impl<T: Ord> Drop for SkewHeap<T> {
     */
 }
 
-struct Node<T: Ord> {
+/// `LazySkewHeap` is a priority queue implemented with skew heap, whose keys can be shifted all at once.
+/// `LazySkewHeap` is a **min-heap**, which means that the entry with the minimum key is popped first.
+///
+/// Each entry is a pair of a key and a value, and only the keys are compared.
+/// [`add_all`](Self::add_all) adds an offset to all keys in O(1) time, like [`LazyLeftistHeap`].
+/// The offset is pushed down to the children of a node when `meld` or `pop` visits the node.
+///
+/// `LazySkewHeap<K, V>` implements [`MeldableHeap<(K, V)>`](MeldableHeap).
+///
+/// # Reference
+/// Sleator, Daniel Dominic; Tarjan, Robert Endre (1986).
+/// ["Self-Adjusting Heaps"][Sleator-Tarjan-1986]
+///
+/// [Sleator-Tarjan-1986]: https://www.cs.cmu.edu/~sleator/papers/Adjusting-Heaps.htm
+/// [`LazyLeftistHeap`]: crate::LazyLeftistHeap
+pub struct LazySkewHeap<K: AdditiveKey, V> {
+    root: Option<Box<LazyNode<K, V>>>,
+    counters: Counters,
+}
+
+// The entries of the lazy heap are ordered by the keys, which are shifted by `AddAll`.
+type LazyNode<K, V> = Node<Entry<K, V>, (), AddAll>;
+
+impl<K: AdditiveKey, V> LazySkewHeap<K, V> {
+    /// Constructs a empty `LazySkewHeap`.
+    /// O(1) time.
+    pub fn new() -> Self {
+        Self {
+            root: None,
+            counters: Counters::default(),
+        }
+    }
+
+    /// Returns `true` if the heap contains no elements.
+    /// O(1) time.
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Inserts an entry into the heap.
+    /// O(log n) amortized time.
+    pub fn push(&mut self, key: K, value: V) {
+        let node = Node::singleton(Entry((key, value)));
+        self.root = Node::meld(self.root.take(), node, &mut self.counters);
+    }
+
+    /// Removes the entry with the minimum key from the heap and returns it, or `None` if it is empty.
+    /// O(log n) amortized time.
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        let mut root = self.root.take()?;
+        root.push_down();
+        self.root = Node::meld(root.left, root.right, &mut self.counters);
+        Some(root.value.0)
+    }
+
+    /// Returns a reference to the entry with the minimum key in the heap, or `None` if it is empty.
+    /// O(1) time.
+    pub fn peek(&self) -> Option<&(K, V)> {
+        self.root.as_ref().map(|node| &node.value.0)
+    }
+
+    /// Adds `delta` to the keys of all entries in the heap.
+    /// O(1) time.
+    pub fn add_all(&mut self, delta: K) {
+        if let Some(root) = &mut self.root {
+            AddAll::apply(&delta, &mut root.value, &mut root.lazy);
+        }
+    }
+
+    /// Melds two heaps into a single heap.
+    /// O(log n) amortized time.
+    pub fn meld(
+        mut heap1: LazySkewHeap<K, V>,
+        mut heap2: LazySkewHeap<K, V>,
+    ) -> LazySkewHeap<K, V> {
+        let mut counters = heap1.counters;
+        counters.absorb(&heap2.counters);
+        let root = Node::meld(heap1.root.take(), heap2.root.take(), &mut counters);
+        Self { root, counters }
+    }
+
+    /// Returns an iterator that visits all entries in the heap with their current keys, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (K, &V)> {
+        let mut stack = Vec::new();
+        if let Some(ref root) = self.root {
+            stack.push((root.as_ref(), K::ZERO));
+        }
+        core::iter::from_fn(move || {
+            // `offset` is the sum of the pending offsets of the ancestors of `node`.
+            let (node, offset) = stack.pop()?;
+            let child_offset = offset.add(node.lazy);
+            for child in [&node.left, &node.right].into_iter().flatten() {
+                stack.push((child.as_ref(), child_offset));
+            }
+            let (key, value) = &node.value.0;
+            Some((key.add(offset), value))
+        })
+    }
+}
+
+impl<K: AdditiveKey, V> Default for LazySkewHeap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[rustfmt::skip]
+impl<K: AdditiveKey, V> MeldableHeap<(K, V)> for LazySkewHeap<K, V> {
+    fn is_empty(&self) -> bool { self.is_empty() }
+    fn push(&mut self, (key, value): (K, V)) { self.push(key, value) }
+    fn pop(&mut self) -> Option<(K, V)> { self.pop() }
+    fn peek(&self) -> Option<&(K, V)> { self.peek() }
+    fn meld(heap1: Self, heap2: Self) -> Self { Self::meld(heap1, heap2) }
+}
+
+impl<K: AdditiveKey, V> From<LazySkewHeap<K, V>> for Vec<(K, V)> {
+    /// Returns the entries of the heap with their current keys in arbitrary order.
+    /// O(n) time.
+    fn from(mut heap: LazySkewHeap<K, V>) -> Self {
+        let mut entries = Vec::new();
+        Node::drain(heap.root.take(), |entry| entries.push(entry.0));
+        entries
+    }
+}
+
+// As for `SkewHeap`, the auto-generated `drop` would cause stack overflow.
+impl<K: AdditiveKey, V> Drop for LazySkewHeap<K, V> {
+    fn drop(&mut self) {
+        Node::drain(self.root.take(), drop);
+    }
+}
+
+struct Node<T: Ord, A: Aggregate<T>, L: Lazy<T> = ()> {
+    // The element is exact if all ancestors have no pending update.
     value: T,
-    left: Option<Box<Node<T>>>,
-    right: Option<Box<Node<T>>>,
+    // The aggregate of the subtree
+    agg: A::Value,
+    // The pending update of the descendants
+    lazy: L::Pending,
+    left: Option<Box<Node<T, A, L>>>,
+    right: Option<Box<Node<T, A, L>>>,
 }
 
-impl<T: Ord> Node<T> {
-    fn singleton(value: T) -> Option<Box<Node<T>>> {
+impl<T: Ord, A: Aggregate<T>, L: Lazy<T>> Node<T, A, L> {
+    fn singleton(value: T) -> Option<Box<Node<T, A, L>>> {
         Some(Box::new(Self {
+            agg: A::lift(&value),
             value,
+            lazy: L::none(),
             left: None,
             right: None,
         }))
     }
 
+    // Apply the pending update to the children, so that their elements become exact.
+    fn push_down(&mut self) {
+        if let Some(update) = L::take(&mut self.lazy) {
+            for child in [&mut self.left, &mut self.right].into_iter().flatten() {
+                L::apply(&update, &mut child.value, &mut child.lazy);
+            }
+        }
+    }
+
+    // Move all elements out of the tree in arbitrary order, and pass them to `f` one-by-one.
+    // Visit all nodes in depth-first order, pushing down the pending updates on the way.
+    //
+    // This implementation reuses heap nodes to create a stack structure.
+    // Therefore, it consumes only O(1) memory except for the heap itself.
+    fn drain(root: Option<Box<Node<T, A, L>>>, mut f: impl FnMut(T)) {
+        let mut stack_top = None;
+        let mut opt_node = root;
+
+        loop {
+            while let Some(mut node) = opt_node {
+                node.push_down();
+                let left = node.left;
+
+                // push node to the stack
+                node.left = stack_top;
+                stack_top = Some(node);
+
+                // move to the left child
+                opt_node = left;
+            }
+
+            // pop a node from the stack
+            let Some(top) = stack_top else { break };
+            let Node {
+                value,
+                agg: _,
+                lazy: _,
+                left,
+                right,
+            } = *top;
+            stack_top = left;
+            opt_node = right;
+
+            // `top` is deallocated here
+            f(value);
+        }
+    }
+
     // `meld` implements `imeld` function from Sleator and Tarjan's paper:
     // https://www.cs.cmu.edu/~sleator/papers/Adjusting-Heaps.htm
-    fn meld(root1: Option<Box<Node<T>>>, root2: Option<Box<Node<T>>>) -> Option<Box<Node<T>>> {
+    //
+    // The subtree of each node on the merged path becomes the union of the two subtrees merged at that node,
+    // so its aggregate is updated on the way down.
+    // The pending update of each node on the merged path is pushed down before its children are swapped,
+    // so that the two nodes compared in each step have exact elements.
+    fn meld(
+        root1: Option<Box<Node<T, A, L>>>,
+        root2: Option<Box<Node<T, A, L>>>,
+        counters: &mut Counters,
+    ) -> Option<Box<Node<T, A, L>>> {
         let (mut root1, mut root2) = match (root1, root2) {
             (None, root2) => return root2,
             (root1, None) => return root1,
             (Some(r1), Some(r2)) => (r1, r2),
         };
 
+        counters.meld();
+        counters.comparison();
+        counters.meld_path(1);
+
         // Ensure root1 <= root2
         if root1.value > root2.value {
             core::mem::swap(&mut root1, &mut root2);
         }
 
         // Skew root
+        root1.agg = A::combine(&root1.agg, &root2.agg);
+        root1.push_down();
         core::mem::swap(&mut root1.left, &mut root1.right);
 
         // Setup loop variables
@@ -153,12 +590,17 @@ impl<T: Ord> Node<T> {
         let mut node2 = root2;
 
         while let Some(mut node1) = node1_opt {
+            counters.comparison();
+            counters.meld_path(1);
+
             // Ensure node1 <= node2
             if node1.value > node2.value {
                 core::mem::swap(&mut node1, &mut node2);
             }
 
             // Skew `node1`
+            node1.agg = A::combine(&node1.agg, &node2.agg);
+            node1.push_down();
             core::mem::swap(&mut node1.left, &mut node1.right);
 
             // Make `node1` the left child of `parent`
@@ -175,11 +617,88 @@ impl<T: Ord> Node<T> {
     }
 }
 
-struct Iter<'a, T: Ord> {
-    stack: Vec<&'a Node<T>>,
+impl<T: Ord, A: Aggregate<T>> BinaryNode<T, A> for Node<T, A> {
+    fn value(&self) -> &T {
+        &self.value
+    }
+
+    fn value_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+
+    fn agg(&self) -> &A::Value {
+        &self.agg
+    }
+
+    fn agg_mut(&mut self) -> &mut A::Value {
+        &mut self.agg
+    }
+
+    fn left(&self) -> Option<&Self> {
+        self.left.as_deref()
+    }
+
+    fn right(&self) -> Option<&Self> {
+        self.right.as_deref()
+    }
+
+    fn children_mut(&mut self) -> (&mut Option<Box<Self>>, &mut Option<Box<Self>>) {
+        (&mut self.left, &mut self.right)
+    }
+
+    fn reset(&mut self) {
+        self.agg = A::lift(&self.value);
+    }
+
+    fn meld(
+        root1: Option<Box<Self>>,
+        root2: Option<Box<Self>>,
+        counters: &mut Counters,
+    ) -> Option<Box<Self>> {
+        Node::meld(root1, root2, counters)
+    }
+}
+
+/// `SkewHeapCursor` is a read-only reference to a node of [`SkewHeap`], returned by [`SkewHeap::cursor`].
+pub struct SkewHeapCursor<'a, T: Ord, A: Aggregate<T> = ()> {
+    node: &'a Node<T, A>,
 }
 
-impl<'a, T: Ord> Iterator for Iter<'a, T> {
+impl<'a, T: Ord, A: Aggregate<T>> SkewHeapCursor<'a, T, A> {
+    /// Returns the element of the node.
+    pub fn value(&self) -> &'a T {
+        &self.node.value
+    }
+
+    /// Returns the aggregate of the subtree of the node.
+    pub fn aggregate(&self) -> &'a A::Value {
+        &self.node.agg
+    }
+
+    /// Returns the left child of the node, or `None` if it has no left child.
+    pub fn left(&self) -> Option<Self> {
+        self.node.left.as_deref().map(|node| Self { node })
+    }
+
+    /// Returns the right child of the node, or `None` if it has no right child.
+    pub fn right(&self) -> Option<Self> {
+        self.node.right.as_deref().map(|node| Self { node })
+    }
+}
+
+impl<T: Ord, A: Aggregate<T>> Clone for SkewHeapCursor<'_, T, A> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Ord, A: Aggregate<T>> Copy for SkewHeapCursor<'_, T, A> {}
+
+struct Iter<'a, T: Ord, A: Aggregate<T>> {
+    stack: Vec<&'a Node<T, A>>,
+}
+
+impl<'a, T: Ord, A: Aggregate<T>> Iterator for Iter<'a, T, A> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -196,81 +715,223 @@ impl<'a, T: Ord> Iterator for Iter<'a, T> {
 
 #[cfg(test)]
 mod tests {
-    use alloc::collections::BinaryHeap;
     use alloc::vec;
     use alloc::vec::Vec;
-    use core::cmp::Reverse;
+    use core::cell::Cell;
 
-    use crate::SkewHeap;
+    use crate::testing::{self, ModelConfig};
+    use crate::{Count, InvariantError, SkewHeap, Sum};
 
     #[test]
     fn basic_test() {
-        let mut heap = SkewHeap::new();
-        for x in [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5, 8, 9, 7, 9] {
-            heap.push(x);
-        }
-        let mut actual = Vec::new();
-        while !heap.is_empty() {
-            let x = heap.pop().unwrap();
-            actual.push(x);
-        }
-        let expected = vec![1, 1, 2, 3, 3, 4, 5, 5, 5, 6, 7, 8, 9, 9, 9];
-        assert_eq!(expected, actual);
+        testing::check_basic::<SkewHeap<u32>>();
     }
 
     #[test]
-    fn drop_test() {
-        let mut heap = SkewHeap::new();
-        for x in [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5, 8, 9, 7, 9] {
-            heap.push(x);
-        }
-        drop(heap);
+    #[cfg_attr(feature = "debug-invariants", ignore)]
+    fn large_drop_test() {
+        testing::check_deep::<SkewHeap<u32>>(1000000);
     }
 
     #[test]
-    fn large_drop_test() {
-        let n = 1000000;
+    fn randomized_test() {
+        let config = ModelConfig {
+            seed: rand::random(),
+            ..ModelConfig::default()
+        };
+        testing::check_model::<SkewHeap<u32>>(&config);
+    }
+
+    #[test]
+    fn validate_test() {
         let mut heap = SkewHeap::new();
-        for i in 0..n {
-            heap.push(n - i);
+        for x in 0..100 {
+            heap.push(Cell::new(x));
         }
-        drop(heap);
+        heap.pop();
+        assert_eq!(heap.validate(), Ok(()));
+
+        // Break the heap order through interior mutability.
+        heap.iter().max().unwrap().set(0);
+        assert!(matches!(
+            heap.validate(),
+            Err(InvariantError::HeapOrder { .. })
+        ));
     }
 
     #[test]
-    fn iter_test() {
+    fn stats_test() {
         let mut heap = SkewHeap::new();
-        for x in [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5, 8, 9, 7, 9] {
+        for x in (0..5).rev() {
             heap.push(x);
         }
-        let mut actual: Vec<_> = heap.iter().copied().collect();
-        actual.sort();
-        let expected = vec![1, 1, 2, 3, 3, 4, 5, 5, 5, 6, 7, 8, 9, 9, 9];
-        assert_eq!(expected, actual);
+        // Each new minimum becomes the root, and the old tree becomes its left child.
+        let stats = heap.stats();
+        assert_eq!(stats.nodes, 5);
+        assert_eq!(stats.height, 5);
+        assert_eq!(stats.right_spine, 1);
+        assert_eq!(stats.max_root_degree, 1);
+        assert_eq!(stats.average_root_degree, 1.0);
+        assert_eq!(stats.max_degree, 1);
+        assert_eq!(stats.average_degree, 1.0);
+
+        let values: Vec<_> = core::iter::successors(heap.cursor(), |cursor| cursor.left())
+            .map(|cursor| *cursor.value())
+            .collect();
+        assert_eq!(values, vec![0, 1, 2, 3, 4]);
+        assert!(heap.cursor().unwrap().right().is_none());
     }
 
     #[test]
-    fn randomized_test() {
-        for _ in 0..1000 {
-            let mut heap = SkewHeap::new();
-            // BinaryHeap is max-heap. So, we need to push Reverse(x) to make it min-heap.
-            let mut expected = BinaryHeap::new();
-            for i in 0..100 {
-                match rand::random::<u32>() % 2 {
+    fn aggregate_test() {
+        for _ in 0..100 {
+            let mut heap = SkewHeap::<u64, (Count, Sum)>::default();
+            let mut expected = Vec::new();
+            for _ in 0..100 {
+                match rand::random::<u32>() % 3 {
                     0 => {
-                        heap.push(i);
-                        expected.push(Reverse(i));
+                        let x = rand::random::<u64>() % 100;
+                        heap.push(x);
+                        expected.push(x);
                     }
                     1 => {
-                        let actual_x = heap.pop();
-                        let expected_x = expected.pop().map(|r| r.0);
-                        assert_eq!(actual_x, expected_x);
+                        if let Some(x) = heap.pop() {
+                            let pos = expected.iter().position(|&y| y == x).unwrap();
+                            expected.swap_remove(pos);
+                        }
+                    }
+                    2 => {
+                        let mut other = SkewHeap::default();
+                        for _ in 0..rand::random::<u32>() % 10 {
+                            let x = rand::random::<u64>() % 100;
+                            other.push(x);
+                            expected.push(x);
+                        }
+                        heap = SkewHeap::meld(other, heap);
                     }
                     _ => unreachable!(),
                 }
-                assert_eq!(expected.is_empty(), heap.is_empty());
-                assert_eq!(expected.peek().map(|r| r.0), heap.peek().copied());
+                assert_eq!(heap.aggregate(), (expected.len(), expected.iter().sum()));
+                let bound = rand::random::<u64>() % 110;
+                let below: Vec<_> = expected.iter().filter(|&&x| x <= bound).collect();
+                assert_eq!(
+                    heap.aggregate_below(&bound),
+                    (below.len(), below.into_iter().sum())
+                );
+            }
+        }
+        assert_eq!(SkewHeap::<u64, Sum>::default().aggregate(), 0);
+    }
+
+    #[test]
+    fn aggregate_below_test() {
+        let mut heap = SkewHeap::<u64, (Count, Sum)>::default();
+        for x in [3, 1, 2, 2, 5] {
+            heap.push(x);
+        }
+        assert_eq!(heap.aggregate_below(&0), (0, 0));
+        assert_eq!(heap.aggregate_below(&1), (1, 1));
+        // The elements equal to the bound are included.
+        assert_eq!(heap.aggregate_below(&2), (3, 5));
+        assert_eq!(heap.aggregate_below(&4), (4, 8));
+        assert_eq!(heap.aggregate_below(&5), heap.aggregate());
+    }
+
+    #[test]
+    fn export_test() {
+        let mut heap = SkewHeap::new();
+        for x in ["a\"b", "c"] {
+            heap.push(x);
+        }
+        let expected = r#"digraph {
+  n0 [label="\"a\\\"b\""];
+  n0 -> n1 [label="L"];
+  n1 [label="\"c\""];
+}
+"#;
+        assert_eq!(heap.to_dot(None), expected);
+        let expected = r#"{"root":{"value":"\"a\\\"b\"","left":{"value":"\"c\"","left":null,"right":null},"right":null}}"#;
+        assert_eq!(heap.to_json(None), expected);
+        assert_eq!(SkewHeap::<u32>::new().to_dot(None), "digraph {\n}\n");
+    }
+
+    #[test]
+    fn below_test() {
+        for _ in 0..100 {
+            let mut heap = SkewHeap::new();
+            let mut expected = Vec::new();
+            for _ in 0..rand::random::<u32>() % 100 {
+                let x = rand::random::<u32>() % 100;
+                heap.push(x);
+                expected.push(x);
+            }
+            // Pop a few elements to give the tree some shape.
+            expected.sort();
+            for x in expected.drain(..expected.len().min(3)) {
+                assert_eq!(heap.pop(), Some(x));
+            }
+            for _ in 0..10 {
+                let x = rand::random::<u32>() % 100;
+                heap.push(x);
+                expected.push(x);
+            }
+            expected.sort();
+
+            let bound = rand::random::<u32>() % 110;
+            let (below, above) = expected.split_at(expected.partition_point(|&x| x <= bound));
+            let mut actual: Vec<_> = heap.iter_below(&bound).copied().collect();
+            actual.sort();
+            assert_eq!(actual, below);
+            assert_eq!(heap.count_below(&bound), below.len());
+
+            let split = heap.split_off_below(&bound);
+            assert_eq!(heap.validate(), Ok(()));
+            assert_eq!(split.validate(), Ok(()));
+            for (mut heap, values) in [(split, below), (heap, above)] {
+                for &x in values {
+                    assert_eq!(heap.pop(), Some(x));
+                }
+                assert!(heap.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn map_test() {
+        let mut heap = SkewHeap::<u64, (Count, Sum)>::default();
+        for x in [3, 1, 4, 1, 5, 9, 2, 6] {
+            heap.push(x);
+        }
+        heap.pop();
+        heap.map_monotone(|x| *x = *x * 10 + 1);
+        assert_eq!(heap.validate(), Ok(()));
+        assert_eq!(heap.aggregate(), (7, 307));
+        assert_eq!(heap.peek(), Some(&11));
+
+        heap.reprioritize(|x| *x = 100 - *x);
+        assert_eq!(heap.validate(), Ok(()));
+        assert_eq!(heap.aggregate(), (7, 393));
+        let mut values = Vec::new();
+        while let Some(x) = heap.pop() {
+            values.push(x);
+        }
+        assert_eq!(values, vec![9, 39, 49, 59, 69, 79, 89]);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "debug-invariants", ignore)]
+    fn map_large_test() {
+        let n = 1000000;
+        for values in [(0..n).collect::<Vec<_>>(), (0..n).rev().collect()] {
+            let mut heap = SkewHeap::<u64, Count>::default();
+            for x in values {
+                heap.push(x);
             }
+            heap.map_monotone(|x| *x += 1);
+            assert_eq!(heap.aggregate(), n as usize);
+            assert_eq!(heap.pop(), Some(1));
+            heap.reprioritize(|x| *x = n - *x);
+            assert_eq!(heap.pop(), Some(0));
         }
     }
 }
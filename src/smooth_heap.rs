@@ -0,0 +1,262 @@
+#![forbid(unsafe_code)]
+
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::MeldableHeap;
+
+/// `SmoothHeap` is a priority queue implemented with smooth heap.
+/// `SmoothHeap` is a **min-heap**, which means that the minimum element is popped first.
+///
+/// When the root is popped, its children are combined by repeatedly linking the leftmost local maximum
+/// to the larger of its neighbors. Children keep their left-to-right order, so that sorted runs in the input
+/// are preserved.
+///
+/// # Reference
+/// - Kozma, László; Saranurak, Thatchaphol (2018).
+///   ["Smooth Heaps and a Dual View of Self-Adjusting Data Structures"][Kozma-Saranurak-2018]
+/// - Hartmann, Maximilian; Kozma, László; Sinnamon, Corwin; Tarjan, Robert E. (2021).
+///   ["Analysis of Smooth Heaps and Slim Heaps"][Hartmann-Kozma-Sinnamon-Tarjan-2021]
+///
+/// [Kozma-Saranurak-2018]: https://arxiv.org/abs/1802.05471
+/// [Hartmann-Kozma-Sinnamon-Tarjan-2021]: https://arxiv.org/abs/2107.04919
+pub struct SmoothHeap<T: Ord> {
+    root: Option<Box<Node<T>>>,
+}
+
+impl<T: Ord> SmoothHeap<T> {
+    /// Constructs a empty `SmoothHeap`.
+    /// O(1) time.
+    pub fn new() -> Self {
+        Self { root: None }
+    }
+
+    /// Returns `true` if the heap contains no elements.
+    /// O(1) time.
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Inserts a value into the heap.
+    /// O(1) time.
+    pub fn push(&mut self, value: T) {
+        self.root = Node::meld(self.root.take(), Node::singleton(value));
+    }
+
+    /// Removes the minimum element from the heap and returns it, or `None` if it is empty.
+    /// O(log n) amortized time.
+    pub fn pop(&mut self) -> Option<T> {
+        let mut root = self.root.take()?;
+        self.root = Node::restructure(root.front.take(), root.back.take());
+        Some(root.value)
+    }
+
+    /// Returns a reference to the minimum element in the heap, or `None` if it is empty.
+    /// O(1) time.
+    pub fn peek(&self) -> Option<&T> {
+        self.root.as_ref().map(|node| &node.value)
+    }
+
+    /// Melds two heaps into a single heap.
+    /// O(1) time.
+    pub fn meld(mut heap1: SmoothHeap<T>, mut heap2: SmoothHeap<T>) -> SmoothHeap<T> {
+        let root = Node::meld(heap1.root.take(), heap2.root.take());
+        Self { root }
+    }
+
+    /// Returns an iterator that visits all elements in the heap, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let mut stack = Vec::new();
+        if let Some(ref node) = self.root {
+            stack.push(node.as_ref());
+        }
+        Iter { stack }
+    }
+}
+
+impl<T: Ord> Default for SmoothHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[rustfmt::skip]
+impl<T: Ord> MeldableHeap<T> for SmoothHeap<T> {
+    fn is_empty(&self) -> bool { self.is_empty() }
+    fn push(&mut self, value: T) { self.push(value) }
+    fn pop(&mut self) -> Option<T> { self.pop() }
+    fn peek(&self) -> Option<&T> { self.peek() }
+    fn meld(heap1: Self, heap2: Self) -> Self { Self::meld(heap1, heap2) }
+}
+
+// We need to implement `drop` for SmoothHeap because auto-generated `drop` would cause stack overflow
+// (the depth of the tree can be O(n) in the worst case).
+impl<T: Ord> Drop for SmoothHeap<T> {
+    // This implementation reuses heap nodes to create a stack structure.
+    // Therefore, it consumes only O(1) memory except for the heap itself.
+    fn drop(&mut self) {
+        let mut stack_top = self.root.take();
+
+        while let Some(mut node) = stack_top {
+            stack_top = node.next.take();
+
+            // move children to the stack
+            for mut list in [node.front.take(), node.back.take()] {
+                while let Some(mut child) = list {
+                    list = child.next.take();
+                    child.next = stack_top;
+                    stack_top = Some(child);
+                }
+            }
+
+            // `node` is deallocated here
+        }
+    }
+}
+
+// Children of a node are kept in two lists, so that a child can be added to either end in O(1) time.
+// The children in left-to-right order are `front` followed by the reverse of `back`.
+struct Node<T: Ord> {
+    value: T,
+    // Leftmost children, from left to right
+    front: Option<Box<Node<T>>>,
+    // Rightmost children, from right to left
+    back: Option<Box<Node<T>>>,
+    next: Option<Box<Node<T>>>,
+}
+
+impl<T: Ord> Node<T> {
+    fn singleton(value: T) -> Option<Box<Node<T>>> {
+        Some(Box::new(Self {
+            value,
+            front: None,
+            back: None,
+            next: None,
+        }))
+    }
+
+    fn push_front(&mut self, mut child: Box<Node<T>>) {
+        child.next = self.front.take();
+        self.front = Some(child);
+    }
+
+    fn push_back(&mut self, mut child: Box<Node<T>>) {
+        child.next = self.back.take();
+        self.back = Some(child);
+    }
+
+    // Link two trees, where `root1` is on the left of `root2`.
+    fn link(mut root1: Box<Node<T>>, mut root2: Box<Node<T>>) -> Box<Node<T>> {
+        if root1.value <= root2.value {
+            root1.push_back(root2);
+            root1
+        } else {
+            root2.push_front(root1);
+            root2
+        }
+    }
+
+    fn meld(root1: Option<Box<Node<T>>>, root2: Option<Box<Node<T>>>) -> Option<Box<Node<T>>> {
+        let (root1, root2) = match (root1, root2) {
+            (None, root2) => return root2,
+            (root1, None) => return root1,
+            (Some(r1), Some(r2)) => (r1, r2),
+        };
+        Some(Self::link(root1, root2))
+    }
+
+    // Combine a list of trees into a single tree by repeatedly linking the leftmost local maximum
+    // to the larger of its neighbors.
+    fn restructure(
+        front: Option<Box<Node<T>>>,
+        back: Option<Box<Node<T>>>,
+    ) -> Option<Box<Node<T>>> {
+        // Trees whose roots are in increasing order from bottom to top, linked by `next`.
+        let mut stack: Option<Box<Node<T>>> = None;
+
+        let mut add = |mut node: Box<Node<T>>| {
+            // While the top of the stack is a local maximum, link it to the larger of its neighbors.
+            while let Some(mut top) = stack.take_if(|top| top.value > node.value) {
+                match top.next.take() {
+                    Some(mut below) if below.value > node.value => {
+                        below.push_back(top);
+                        stack = Some(below);
+                    }
+                    below => {
+                        node.push_front(top);
+                        stack = below;
+                    }
+                }
+            }
+            node.next = stack.take();
+            stack = Some(node);
+        };
+
+        // Add the trees from left to right.
+        let mut it = front;
+        while let Some(mut node) = it {
+            it = node.next.take();
+            add(node);
+        }
+        let mut reversed = None;
+        let mut it = back;
+        while let Some(mut node) = it {
+            it = node.next.take();
+            node.next = reversed;
+            reversed = Some(node);
+        }
+        let mut it = reversed;
+        while let Some(mut node) = it {
+            it = node.next.take();
+            add(node);
+        }
+
+        // The remaining local maximum is the top of the stack. Link it to its left neighbor.
+        let mut top = stack?;
+        while let Some(mut below) = top.next.take() {
+            below.push_back(top);
+            top = below;
+        }
+        Some(top)
+    }
+}
+
+struct Iter<'a, T: Ord> {
+    stack: Vec<&'a Node<T>>,
+}
+
+impl<'a, T: Ord> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        for next in [&node.front, &node.back, &node.next].into_iter().flatten() {
+            self.stack.push(next);
+        }
+        Some(&node.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::{self, ModelConfig};
+    use crate::SmoothHeap;
+
+    #[test]
+    fn basic_test() {
+        testing::check_basic::<SmoothHeap<u32>>();
+    }
+
+    #[test]
+    fn large_drop_test() {
+        testing::check_deep::<SmoothHeap<u32>>(1000000);
+    }
+
+    #[test]
+    fn randomized_test() {
+        let config = ModelConfig {
+            seed: rand::random(),
+            ..ModelConfig::default()
+        };
+        testing::check_model::<SmoothHeap<u32>>(&config);
+    }
+}
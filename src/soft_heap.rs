@@ -0,0 +1,615 @@
+#![forbid(unsafe_code)]
+
+use alloc::vec::Vec;
+
+/// `SoftHeap` is an approximate priority queue implemented with soft heap.
+/// `SoftHeap` is a **min-heap** whose keys may be *corrupted*, i.e. raised above their original values.
+///
+/// Items sharing a node share a single key, which is the greatest original key among them.
+/// `pop` removes an item with the minimum current key, and returns the current key together with the original item.
+/// An item is corrupted if its current key is greater than the item itself.
+/// At any time, at most εn items in the heap are corrupted, where n is the number of items pushed so far.
+///
+/// In exchange for the corruption, `push` takes O(log(1/ε)) amortized time regardless of the size of the heap,
+/// which makes linear-time selection and minimum spanning tree algorithms possible.
+///
+/// Nodes and items are stored in vectors and linked by indices, so that item lists are concatenated in O(1) time.
+///
+/// # Reference
+/// - Chazelle, Bernard (2000).
+///   ["The Soft Heap: An Approximate Priority Queue with Optimal Error Rate"][Chazelle-2000]
+/// - Kaplan, Haim; Tarjan, Robert E.; Zwick, Uri (2013).
+///   ["Soft Heaps Simplified"][Kaplan-Tarjan-Zwick-2013]
+///
+/// [Chazelle-2000]: https://doi.org/10.1145/355541.355554
+/// [Kaplan-Tarjan-Zwick-2013]: https://doi.org/10.1137/120880185
+pub struct SoftHeap<T: Ord + Clone> {
+    nodes: Vec<Node<T>>,
+    free_nodes: Vec<usize>,
+    items: Vec<Item<T>>,
+    free_items: Vec<usize>,
+    // The root of the lowest rank. Roots are linked by `next` in increasing order of rank.
+    first: Option<usize>,
+    // Nodes of rank at most `threshold` hold a single item, and never corrupt keys.
+    threshold: u8,
+    len: usize,
+}
+
+impl<T: Ord + Clone> SoftHeap<T> {
+    /// Constructs a empty `SoftHeap` with the error rate `epsilon`.
+    /// O(1) time.
+    ///
+    /// # Panics
+    /// Panics if `epsilon` is not in the range (0, 1].
+    pub fn new(epsilon: f64) -> Self {
+        assert!(
+            epsilon > 0.0 && epsilon <= 1.0,
+            "new: epsilon must be in the range (0, 1]"
+        );
+
+        // threshold = ceil(log2(1/ε)) + 5
+        let mut threshold = 5;
+        let mut rate = 1.0;
+        while rate > epsilon {
+            rate /= 2.0;
+            threshold += 1;
+        }
+
+        Self {
+            nodes: Vec::new(),
+            free_nodes: Vec::new(),
+            items: Vec::new(),
+            free_items: Vec::new(),
+            first: None,
+            threshold,
+            len: 0,
+        }
+    }
+
+    /// Returns `true` if the heap contains no elements.
+    /// O(1) time.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of elements in the heap.
+    /// O(1) time.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Inserts an item into the heap.
+    /// O(log(1/ε)) amortized time.
+    pub fn push(&mut self, value: T) {
+        let item = self.alloc_item(value.clone());
+        let node = self.alloc_node(Node {
+            key: Some(value),
+            rank: 0,
+            size: 1,
+            left: None,
+            right: None,
+            head: Some(item),
+            tail: Some(item),
+            count: 1,
+            next: None,
+            suffix_min: 0,
+        });
+        self.nodes[node].suffix_min = node;
+        self.first = self.meld_roots(self.first, Some(node));
+        self.len += 1;
+    }
+
+    /// Removes an item with the minimum current key from the heap and returns `(key, item)`,
+    /// or `None` if it is empty.
+    /// The item is corrupted if `key` is greater than `item`.
+    /// O(log n) amortized time.
+    pub fn pop(&mut self) -> Option<(T, T)> {
+        let first = self.first?;
+        let min = self.nodes[first].suffix_min;
+
+        // Take the first item of the root with the minimum key.
+        let item = self.nodes[min].head.unwrap();
+        self.nodes[min].head = self.items[item].next;
+        self.nodes[min].count -= 1;
+        let value = self.items[item].value.take().unwrap();
+        self.free_items.push(item);
+        let key = self.key(min).clone();
+        self.len -= 1;
+
+        if self.nodes[min].count == 0 {
+            self.nodes[min].tail = None;
+
+            // Collect the roots preceding `min`, whose suffix minimums may change.
+            let mut prefix = Vec::new();
+            let mut it = first;
+            while it != min {
+                prefix.push(it);
+                it = self.nodes[it].next.unwrap();
+            }
+
+            if self.is_leaf(min) {
+                let next = self.nodes[min].next;
+                match prefix.last() {
+                    Some(&prev) => self.nodes[prev].next = next,
+                    None => self.first = next,
+                }
+                self.free_node(min);
+            } else {
+                self.sift(min);
+                prefix.push(min);
+            }
+            self.update_suffix_min(&prefix);
+        }
+
+        Some((key, value))
+    }
+
+    /// Melds two heaps into a single heap.
+    /// The error rate of the result is the greater of the error rates of the two heaps.
+    /// O(log n) amortized time, plus O(min(n, m)) time to move the nodes and items of the smaller heap
+    /// into the storage of the larger one, where n and m count the nodes and items in use.
+    /// Free slots of the smaller heap are not moved.
+    pub fn meld(mut heap1: SoftHeap<T>, mut heap2: SoftHeap<T>) -> SoftHeap<T> {
+        if heap1.storage_len() < heap2.storage_len() {
+            core::mem::swap(&mut heap1, &mut heap2);
+        }
+
+        let first2 = heap1.move_from(&mut heap2);
+        heap1.threshold = heap1.threshold.min(heap2.threshold);
+        heap1.len += heap2.len;
+        heap1.first = heap1.meld_roots(heap1.first, first2);
+        heap1
+    }
+
+    /// Returns an iterator that visits all items in the heap, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.items.iter().filter_map(|item| item.value.as_ref())
+    }
+
+    /// Returns an iterator that visits all corrupted items in the heap, in arbitrary order.
+    pub fn corrupted(&self) -> impl Iterator<Item = &T> {
+        self.nodes.iter().flat_map(move |node| {
+            core::iter::successors(node.head, move |&item| self.items[item].next)
+                .map(move |item| self.items[item].value.as_ref().unwrap())
+                .filter(move |&value| Some(value) < node.key.as_ref())
+        })
+    }
+
+    fn key(&self, node: usize) -> &T {
+        self.nodes[node].key.as_ref().unwrap()
+    }
+
+    fn is_leaf(&self, node: usize) -> bool {
+        self.nodes[node].left.is_none() && self.nodes[node].right.is_none()
+    }
+
+    // The number of nodes and items in use, i.e. the cost of moving the heap into another one.
+    fn storage_len(&self) -> usize {
+        self.nodes.len() - self.free_nodes.len() + self.len
+    }
+
+    // Move the nodes and items of `heap` into the storage of `self`, and return the first root of `heap` in it.
+    // The nodes are visited from the roots, so that the free slots of `heap` are not moved,
+    // and the free slots of `self` are reused.
+    fn move_from(&mut self, heap: &mut SoftHeap<T>) -> Option<usize> {
+        let mut roots = Vec::new();
+        // The moved nodes whose children still refer to the nodes of `heap`
+        let mut stack = Vec::new();
+        let mut root = heap.first;
+        while let Some(old) = root {
+            root = heap.nodes[old].next;
+            let new = self.move_node(heap, old);
+            roots.push(new);
+            stack.push(new);
+        }
+
+        while let Some(node) = stack.pop() {
+            if let Some(old) = self.nodes[node].left {
+                let new = self.move_node(heap, old);
+                self.nodes[node].left = Some(new);
+                stack.push(new);
+            }
+            if let Some(old) = self.nodes[node].right {
+                let new = self.move_node(heap, old);
+                self.nodes[node].right = Some(new);
+                stack.push(new);
+            }
+        }
+
+        for window in roots.windows(2) {
+            self.nodes[window[0]].next = Some(window[1]);
+        }
+        self.update_suffix_min(&roots);
+        roots.first().copied()
+    }
+
+    // Move a node of `heap` and its item list into the storage of `self`.
+    // The children of the moved node still refer to the nodes of `heap`.
+    fn move_node(&mut self, heap: &mut SoftHeap<T>, node: usize) -> usize {
+        let mut head = None;
+        let mut tail: Option<usize> = None;
+        let mut item = heap.nodes[node].head;
+        while let Some(old) = item {
+            item = heap.items[old].next;
+            let new = self.alloc_item(heap.items[old].value.take().unwrap());
+            match tail {
+                Some(tail) => self.items[tail].next = Some(new),
+                None => head = Some(new),
+            }
+            tail = Some(new);
+        }
+
+        let node = &mut heap.nodes[node];
+        self.alloc_node(Node {
+            key: node.key.take(),
+            rank: node.rank,
+            size: node.size,
+            left: node.left,
+            right: node.right,
+            head,
+            tail,
+            count: node.count,
+            next: None,
+            suffix_min: 0,
+        })
+    }
+
+    fn alloc_item(&mut self, value: T) -> usize {
+        let item = Item {
+            value: Some(value),
+            next: None,
+        };
+        if let Some(index) = self.free_items.pop() {
+            self.items[index] = item;
+            index
+        } else {
+            self.items.push(item);
+            self.items.len() - 1
+        }
+    }
+
+    fn alloc_node(&mut self, node: Node<T>) -> usize {
+        if let Some(index) = self.free_nodes.pop() {
+            self.nodes[index] = node;
+            index
+        } else {
+            self.nodes.push(node);
+            self.nodes.len() - 1
+        }
+    }
+
+    fn free_node(&mut self, node: usize) {
+        self.nodes[node].key = None;
+        self.nodes[node].left = None;
+        self.nodes[node].right = None;
+        self.nodes[node].next = None;
+        self.free_nodes.push(node);
+    }
+
+    // Link two roots of the same rank under a new root, and fill the item list of the new root.
+    fn link(&mut self, root1: usize, root2: usize) -> usize {
+        let rank = self.nodes[root1].rank + 1;
+        let size = if rank <= self.threshold {
+            1
+        } else {
+            (3 * self.nodes[root1].size).div_ceil(2)
+        };
+        let node = self.alloc_node(Node {
+            key: None,
+            rank,
+            size,
+            left: Some(root1),
+            right: Some(root2),
+            head: None,
+            tail: None,
+            count: 0,
+            next: None,
+            suffix_min: 0,
+        });
+        self.sift(node);
+        node
+    }
+
+    // Move item lists up from the children until the item list of `node` is large enough.
+    fn sift(&mut self, node: usize) {
+        // The ancestors of `current` whose sifting is suspended.
+        let mut stack = Vec::new();
+        let mut current = node;
+
+        loop {
+            if self.nodes[current].count >= self.nodes[current].size || self.is_leaf(current) {
+                match stack.pop() {
+                    Some(parent) => current = parent,
+                    None => break,
+                }
+                continue;
+            }
+
+            // Ensure the left child has the smaller key.
+            let Node { left, right, .. } = self.nodes[current];
+            let swap = match (left, right) {
+                (None, _) => true,
+                (Some(left), Some(right)) => self.key(left) > self.key(right),
+                _ => false,
+            };
+            if swap {
+                self.nodes[current].left = right;
+                self.nodes[current].right = left;
+            }
+            let child = self.nodes[current].left.unwrap();
+
+            // Move the item list of `child` to the end of the item list of `current`.
+            // The key of `child` is not used anymore, because `child` is either discarded or sifted.
+            let head = self.nodes[child].head.take();
+            let tail = self.nodes[child].tail.take();
+            match self.nodes[current].tail {
+                Some(current_tail) => self.items[current_tail].next = head,
+                None => self.nodes[current].head = head,
+            }
+            self.nodes[current].tail = tail;
+            self.nodes[current].count += core::mem::take(&mut self.nodes[child].count);
+            self.nodes[current].key = self.nodes[child].key.take();
+
+            if self.is_leaf(child) {
+                self.nodes[current].left = None;
+                self.free_node(child);
+            } else {
+                stack.push(current);
+                current = child;
+            }
+        }
+    }
+
+    // Meld two root lists like adding binary numbers, and return the first root of the result.
+    fn meld_roots(&mut self, list1: Option<usize>, list2: Option<usize>) -> Option<usize> {
+        // The roots of the result, except for `tail` which is not modified.
+        let mut prefix = Vec::new();
+        let mut carry: Option<usize> = None;
+        let (mut list1, mut list2) = (list1, list2);
+
+        let tail = loop {
+            let Some(c) = carry else {
+                let (Some(root1), Some(root2)) = (list1, list2) else {
+                    break list1.or(list2);
+                };
+                let (rank1, rank2) = (self.nodes[root1].rank, self.nodes[root2].rank);
+                if rank1 < rank2 {
+                    prefix.push(root1);
+                    list1 = self.nodes[root1].next;
+                } else if rank2 < rank1 {
+                    prefix.push(root2);
+                    list2 = self.nodes[root2].next;
+                } else {
+                    list1 = self.nodes[root1].next;
+                    list2 = self.nodes[root2].next;
+                    carry = Some(self.link(root1, root2));
+                }
+                continue;
+            };
+
+            let rank = self.nodes[c].rank;
+            let root1 = list1.filter(|&root| self.nodes[root].rank == rank);
+            let root2 = list2.filter(|&root| self.nodes[root].rank == rank);
+            match (root1, root2) {
+                (Some(root1), Some(root2)) => {
+                    prefix.push(c);
+                    list1 = self.nodes[root1].next;
+                    list2 = self.nodes[root2].next;
+                    carry = Some(self.link(root1, root2));
+                }
+                (Some(root), None) | (None, Some(root)) => {
+                    if root1.is_some() {
+                        list1 = self.nodes[root].next;
+                    } else {
+                        list2 = self.nodes[root].next;
+                    }
+                    carry = Some(self.link(c, root));
+                }
+                (None, None) => {
+                    prefix.push(c);
+                    carry = None;
+                }
+            }
+        };
+
+        for window in prefix.windows(2) {
+            self.nodes[window[0]].next = Some(window[1]);
+        }
+        let Some(&last) = prefix.last() else {
+            return tail;
+        };
+        self.nodes[last].next = tail;
+        self.update_suffix_min(&prefix);
+        prefix.first().copied()
+    }
+
+    // Recompute the suffix minimums of consecutive roots `roots`, assuming those of the following roots are valid.
+    fn update_suffix_min(&mut self, roots: &[usize]) {
+        for &root in roots.iter().rev() {
+            let mut suffix_min = root;
+            if let Some(next) = self.nodes[root].next {
+                let next_min = self.nodes[next].suffix_min;
+                if self.key(next_min) < self.key(root) {
+                    suffix_min = next_min;
+                }
+            }
+            self.nodes[root].suffix_min = suffix_min;
+        }
+    }
+}
+
+struct Node<T> {
+    // The current key of the items in the list. `None` if the node is in the free list.
+    key: Option<T>,
+    rank: u8,
+    // The target number of items in the list
+    size: usize,
+    left: Option<usize>,
+    right: Option<usize>,
+    // The item list
+    head: Option<usize>,
+    tail: Option<usize>,
+    count: usize,
+    // The next root, if the node is a root
+    next: Option<usize>,
+    // The root with the minimum key among this root and the following roots, if the node is a root
+    suffix_min: usize,
+}
+
+struct Item<T> {
+    // `None` if the item is in the free list
+    value: Option<T>,
+    next: Option<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BinaryHeap;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use core::cmp::Reverse;
+
+    use crate::SoftHeap;
+
+    #[test]
+    fn basic_test() {
+        let mut heap = SoftHeap::new(0.01);
+        for x in [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5, 8, 9, 7, 9] {
+            heap.push(x);
+        }
+        assert_eq!(heap.len(), 15);
+        let mut actual = Vec::new();
+        while !heap.is_empty() {
+            let (key, x) = heap.pop().unwrap();
+            assert_eq!(key, x);
+            actual.push(x);
+        }
+        let expected = vec![1, 1, 2, 3, 3, 4, 5, 5, 5, 6, 7, 8, 9, 9, 9];
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn iter_test() {
+        let mut heap = SoftHeap::new(0.01);
+        for x in [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5, 8, 9, 7, 9] {
+            heap.push(x);
+        }
+        let mut actual: Vec<_> = heap.iter().copied().collect();
+        actual.sort();
+        let expected = vec![1, 1, 2, 3, 3, 4, 5, 5, 5, 6, 7, 8, 9, 9, 9];
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn corruption_test() {
+        for epsilon in [1.0, 0.5, 0.1, 0.01] {
+            let n = 20000;
+            let mut heap = SoftHeap::new(epsilon);
+            let mut pushed = Vec::new();
+            for _ in 0..n {
+                let x = rand::random::<u32>();
+                heap.push(x);
+                pushed.push(x);
+            }
+            assert!(heap.corrupted().count() as f64 <= epsilon * n as f64);
+
+            // Keys are popped in increasing order, and each key bounds its item.
+            let mut popped = Vec::new();
+            let mut last_key = 0;
+            while let Some((key, x)) = heap.pop() {
+                assert!(last_key <= key);
+                assert!(x <= key);
+                last_key = key;
+                popped.push(x);
+                if popped.len() % 1000 == 0 {
+                    assert!(heap.corrupted().count() as f64 <= epsilon * n as f64);
+                }
+            }
+            pushed.sort();
+            popped.sort();
+            assert_eq!(pushed, popped);
+        }
+    }
+
+    #[test]
+    fn meld_test() {
+        let mut heap1 = SoftHeap::new(0.1);
+        let mut heap2 = SoftHeap::new(0.1);
+        for i in 0..1000 {
+            heap1.push(2 * i);
+            heap2.push(2 * i + 1);
+        }
+        let mut heap = SoftHeap::meld(heap1, heap2);
+        assert_eq!(heap.len(), 2000);
+        assert!(heap.corrupted().count() <= 200);
+
+        let mut popped: Vec<_> = core::iter::from_fn(|| heap.pop()).map(|(_, x)| x).collect();
+        popped.sort();
+        assert_eq!(popped, (0..2000).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn meld_storage_test() {
+        // `heap1` has many free slots left by the pops, but fewer items than `heap2`.
+        let mut heap1 = SoftHeap::new(0.1);
+        for i in 0..10000 {
+            heap1.push(i);
+        }
+        for _ in 0..9990 {
+            heap1.pop();
+        }
+        let mut heap2 = SoftHeap::new(0.1);
+        for i in 0..100 {
+            heap2.push(i);
+        }
+        let items = heap2.items.len();
+
+        // Only the items and nodes in use are moved into the storage of `heap2`, not the slots of 10000 items.
+        let mut heap = SoftHeap::meld(heap1, heap2);
+        assert_eq!(heap.len(), 110);
+        assert_eq!(heap.items.len(), items + 10);
+        assert!(heap.nodes.len() < 1000);
+
+        let mut popped: Vec<_> = core::iter::from_fn(|| heap.pop()).map(|(_, x)| x).collect();
+        popped.sort();
+        let mut expected: Vec<_> = (0..100).chain(9990..10000).collect();
+        expected.sort();
+        assert_eq!(popped, expected);
+    }
+
+    #[test]
+    fn randomized_test() {
+        for _ in 0..1000 {
+            // Keys are never corrupted if ε is small enough for the size of the heap.
+            let mut heap = SoftHeap::new(0.01);
+            // BinaryHeap is max-heap. So, we need to push Reverse(x) to make it min-heap.
+            let mut expected = BinaryHeap::new();
+            for _ in 0..100 {
+                match rand::random::<u32>() % 3 {
+                    0 => {
+                        let x = rand::random::<u32>() % 100;
+                        heap.push(x);
+                        expected.push(Reverse(x));
+                    }
+                    1 => {
+                        let actual_x = heap.pop().map(|(_, x)| x);
+                        let expected_x = expected.pop().map(|r| r.0);
+                        assert_eq!(actual_x, expected_x);
+                    }
+                    2 => {
+                        let mut other = SoftHeap::new(0.01);
+                        for _ in 0..rand::random::<u32>() % 10 {
+                            let x = rand::random::<u32>() % 100;
+                            other.push(x);
+                            expected.push(Reverse(x));
+                        }
+                        heap = SoftHeap::meld(heap, other);
+                    }
+                    _ => unreachable!(),
+                }
+                assert_eq!(expected.len(), heap.len());
+                assert_eq!(heap.corrupted().count(), 0);
+            }
+        }
+    }
+}
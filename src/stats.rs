@@ -0,0 +1,83 @@
+#![forbid(unsafe_code)]
+
+use alloc::vec::Vec;
+
+/// `HeapStats` describes the shape of a heap at some point, returned by `stats`.
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct HeapStats {
+    /// The number of nodes.
+    pub nodes: usize,
+    /// The number of nodes on the longest path from a root to a leaf.
+    pub height: usize,
+    /// The number of nodes on the path from the root following the rightmost children.
+    pub right_spine: usize,
+    /// The maximum number of children of a root.
+    /// `PairingHeap` can have several roots, which are the root of the main tree and the trees in the auxiliary list.
+    pub max_root_degree: usize,
+    /// The average number of children of the roots.
+    pub average_root_degree: f64,
+    /// The average number of children of the nodes which have at least one child.
+    pub average_degree: f64,
+    /// The maximum number of children of a node.
+    pub max_degree: usize,
+    /// `rank_counts[r]` is the number of nodes of rank `r`. It is empty for heaps without ranks.
+    pub rank_counts: Vec<usize>,
+    /// The number of bytes used by the heap and its nodes, excluding the heap memory owned by the elements.
+    pub memory: usize,
+}
+
+// `StatsBuilder` accumulates `HeapStats` while the nodes are visited.
+#[derive(Default)]
+pub(crate) struct StatsBuilder {
+    stats: HeapStats,
+    internal_nodes: usize,
+    total_degree: usize,
+    roots: usize,
+    total_root_degree: usize,
+}
+
+impl StatsBuilder {
+    // Record a node at `depth` (the root is at depth 1) with `degree` children.
+    pub(crate) fn node(&mut self, depth: usize, degree: usize) {
+        let stats = &mut self.stats;
+        stats.nodes += 1;
+        stats.height = stats.height.max(depth);
+        stats.max_degree = stats.max_degree.max(degree);
+        if depth == 1 {
+            stats.max_root_degree = stats.max_root_degree.max(degree);
+            self.roots += 1;
+            self.total_root_degree += degree;
+        }
+        if degree > 0 {
+            self.internal_nodes += 1;
+            self.total_degree += degree;
+        }
+    }
+
+    pub(crate) fn rank(&mut self, rank: usize) {
+        let counts = &mut self.stats.rank_counts;
+        if counts.len() <= rank {
+            counts.resize(rank + 1, 0);
+        }
+        counts[rank] += 1;
+    }
+
+    // `heap_size` is the size of the heap itself, and `node_size` is the size of a node.
+    pub(crate) fn finish(
+        mut self,
+        right_spine: usize,
+        heap_size: usize,
+        node_size: usize,
+    ) -> HeapStats {
+        let stats = &mut self.stats;
+        stats.right_spine = right_spine;
+        stats.memory = heap_size + stats.nodes * node_size;
+        if self.internal_nodes > 0 {
+            stats.average_degree = self.total_degree as f64 / self.internal_nodes as f64;
+        }
+        if self.roots > 0 {
+            stats.average_root_degree = self.total_root_degree as f64 / self.roots as f64;
+        }
+        self.stats
+    }
+}
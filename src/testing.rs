@@ -0,0 +1,343 @@
+//! Conformance checks for implementations of [`MeldableHeap`] and [`AddressableHeap`].
+//!
+//! The checks panic with the seed, the round and the step of the first mismatch, so that a failure can be
+//! reproduced by running the check again with the same [`ModelConfig`].
+//! This module is available with the `testing` feature.
+
+#![forbid(unsafe_code)]
+
+use alloc::collections::{BTreeMap, BinaryHeap};
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+use core::fmt;
+
+use crate::{
+    AdaptiveHeap, AddressableHeap, Aggregate, AnyHeap, BottomUpSkewHeap, CalendarQueue, Clock,
+    DoubleEndedPairingHeap, FibonacciHeap, HollowHeap, LeftistHeap, MeldableHeap, PairingHeap,
+    PairingStrategy, RadixHeap, RadixKey, RandomSource, RandomizedMeldableHeap, RankPairingHeap,
+    RankRule, SkewBinomialHeap, SkewHeap, SmoothHeap, SplitMix64, Timestamped,
+    WeightBiasedLeftistHeap,
+};
+
+/// `Inspect` exposes the contents of a heap to the checks in this module.
+pub trait Inspect<T>: MeldableHeap<T> {
+    /// Returns the number of elements, or `None` if the heap does not keep track of it.
+    fn len(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns references to all elements in arbitrary order.
+    fn elements(&self) -> Vec<&T>;
+}
+
+macro_rules! impl_inspect {
+    ($([$($generics:tt)*] $heap:ty, $t:ty, $len:tt;)*) => {
+        $(
+            impl<$($generics)*> Inspect<$t> for $heap {
+                impl_inspect!(@len $len);
+
+                fn elements(&self) -> Vec<&$t> {
+                    self.iter().collect()
+                }
+            }
+        )*
+    };
+    (@len len) => {
+        fn len(&self) -> Option<usize> {
+            Some(self.len())
+        }
+    };
+    (@len -) => {};
+}
+
+impl_inspect! {
+    [T: Ord, S: PairingStrategy] PairingHeap<T, S>, T, -;
+    [T: Ord, A: Aggregate<T>] LeftistHeap<T, A>, T, -;
+    [T: Ord, A: Aggregate<T>] SkewHeap<T, A>, T, -;
+    [T: Ord] AnyHeap<T>, T, -;
+    [T: Ord, C: Clock + Default] AdaptiveHeap<T, C>, T, len;
+    [T: Ord, R: RankRule] RankPairingHeap<T, R>, T, len;
+    [T: Ord] HollowHeap<T>, T, len;
+    [T: Ord] FibonacciHeap<T>, T, len;
+    [T: Ord] SkewBinomialHeap<T>, T, -;
+    [T: Ord] WeightBiasedLeftistHeap<T>, T, len;
+    [T: Ord, R: RandomSource] RandomizedMeldableHeap<T, R>, T, -;
+    [T: Ord] BottomUpSkewHeap<T>, T, len;
+    [T: Ord] SmoothHeap<T>, T, -;
+    [T: Ord] DoubleEndedPairingHeap<T>, T, len;
+    [T: Timestamped] CalendarQueue<T>, T, len;
+    [K: RadixKey, V] RadixHeap<K, V>, (K, V), len;
+}
+
+/// `ModelConfig` controls the randomized checks [`check_model`] and [`check_addressable`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ModelConfig {
+    /// The seed of the random operations. Default: 0.
+    pub seed: u64,
+    /// The number of heaps built from scratch. Default: 1000.
+    pub rounds: usize,
+    /// The number of operations on each heap. Default: 100.
+    pub ops: usize,
+    /// The pushed values are less than this value. Default: 100.
+    pub max_value: u32,
+}
+
+impl Default for ModelConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            rounds: 1000,
+            ops: 100,
+            max_value: 100,
+        }
+    }
+}
+
+// `Step` locates an operation in the panic messages.
+#[derive(Clone, Copy)]
+struct Step {
+    seed: u64,
+    round: usize,
+    step: usize,
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "seed {}, round {}, step {}",
+            self.seed, self.round, self.step
+        )
+    }
+}
+
+const VALUES: [u32; 15] = [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5, 8, 9, 7, 9];
+
+/// Pushes a fixed sequence with duplicates, and checks the elements and the order of the pops.
+pub fn check_basic<H: Inspect<u32>>() {
+    let mut heap = H::default();
+    assert!(heap.is_empty());
+    assert_eq!(heap.peek(), None);
+    assert_eq!(heap.pop(), None);
+
+    for x in VALUES {
+        heap.push(x);
+    }
+    let mut expected = VALUES.to_vec();
+    expected.sort();
+    assert_eq!(heap.len().unwrap_or(VALUES.len()), VALUES.len());
+    assert_eq!(sorted(heap.elements()), expected);
+    assert_eq!(heap.peek(), Some(&1));
+
+    let mut actual = Vec::new();
+    while !heap.is_empty() {
+        actual.push(heap.pop().unwrap());
+    }
+    assert_eq!(actual, expected);
+    assert_eq!(heap.pop(), None);
+}
+
+/// Runs random pushes, pops and melds against `BinaryHeap`, and compares `is_empty`, `peek` and `len`
+/// after each operation, and the elements after each meld and at the end of each round.
+pub fn check_model<H: Inspect<u32>>(config: &ModelConfig) {
+    let mut rng = SplitMix64::seed_from_u64(config.seed);
+    for round in 0..config.rounds {
+        let mut heap = H::default();
+        // BinaryHeap is max-heap. So, we need to push Reverse(x) to make it min-heap.
+        let mut model = BinaryHeap::new();
+        for step in 0..config.ops {
+            let at = Step {
+                seed: config.seed,
+                round,
+                step,
+            };
+            let op = rng.next_u64() % 4;
+            let melded = op == 3;
+            match op {
+                0 | 1 => {
+                    let x = random_value(&mut rng, config);
+                    heap.push(x);
+                    model.push(Reverse(x));
+                }
+                2 => {
+                    let expected = model.pop().map(|r| r.0);
+                    assert_eq!(heap.pop(), expected, "pop at {at}");
+                }
+                3 => {
+                    let mut other = H::default();
+                    for _ in 0..rng.next_u64() % 10 {
+                        let x = random_value(&mut rng, config);
+                        other.push(x);
+                        model.push(Reverse(x));
+                    }
+                    // Meld in both orders, since some heaps treat their arguments differently.
+                    heap = if rng.next_u64() % 2 == 0 {
+                        H::meld(heap, other)
+                    } else {
+                        H::meld(other, heap)
+                    };
+                }
+                _ => unreachable!(),
+            }
+            assert_eq!(heap.is_empty(), model.is_empty(), "is_empty at {at}");
+            assert_eq!(heap.peek(), model.peek().map(|r| &r.0), "peek at {at}");
+            if let Some(len) = heap.len() {
+                assert_eq!(len, model.len(), "len at {at}");
+            }
+            if melded || step + 1 == config.ops {
+                let expected: Vec<_> = model.iter().map(|r| r.0).collect();
+                assert_eq!(
+                    sorted(heap.elements()),
+                    sorted_values(expected),
+                    "elements at {at}"
+                );
+            }
+        }
+    }
+}
+
+/// Runs random pushes, pops, `decrease_key`, `remove` and `get` against a model, and compares
+/// the elements of all live handles after each operation.
+///
+/// The pushed values are distinct, so `max_value * (ops + 1)` must fit in `u32`.
+pub fn check_addressable<H: AddressableHeap<u32>>(config: &ModelConfig) {
+    // A value is `key * stride + id`, where `id` is unique in a round and is kept by `decrease_key`.
+    let stride = config.ops as u64 + 1;
+    assert!(
+        config.max_value as u64 * stride <= u32::MAX as u64,
+        "max_value * (ops + 1) must fit in u32"
+    );
+    let mut rng = SplitMix64::seed_from_u64(config.seed);
+    for round in 0..config.rounds {
+        let mut heap = H::default();
+        let mut model = BTreeMap::new();
+        for step in 0..config.ops {
+            let at = Step {
+                seed: config.seed,
+                round,
+                step,
+            };
+            let nth = |rng: &mut SplitMix64, model: &BTreeMap<u32, _>| {
+                let k = rng.next_u64() as usize % model.len();
+                model.iter().nth(k).map(|(&x, &h)| (x, h)).unwrap()
+            };
+            match rng.next_u64() % 5 {
+                0 | 1 => {
+                    let x =
+                        (random_value(&mut rng, config) as u64 * stride) as u32 + step as u32 + 1;
+                    model.insert(x, heap.push_with_handle(x));
+                }
+                2 => {
+                    let expected = model.pop_first().map(|(x, _)| x);
+                    assert_eq!(heap.pop(), expected, "pop at {at}");
+                }
+                3 if !model.is_empty() => {
+                    let (x, handle) = nth(&mut rng, &model);
+                    let d = rng.next_u64() % (x as u64 / stride + 1);
+                    let new_x = x - (d * stride) as u32;
+                    heap.decrease_key(handle, new_x);
+                    model.remove(&x);
+                    model.insert(new_x, handle);
+                }
+                4 if !model.is_empty() => {
+                    let (x, handle) = nth(&mut rng, &model);
+                    assert_eq!(heap.remove(handle), Some(x), "remove at {at}");
+                    model.remove(&x);
+                }
+                _ => {}
+            }
+            assert_eq!(heap.is_empty(), model.is_empty(), "is_empty at {at}");
+            assert_eq!(heap.peek(), model.keys().next(), "peek at {at}");
+            for (x, &handle) in &model {
+                assert_eq!(heap.get(handle), Some(x), "get at {at}");
+            }
+        }
+    }
+}
+
+/// Builds heaps of `n` elements which are deep for some heaps, and drops them,
+/// to check that no operation recurses on the depth of a tree.
+pub fn check_deep<H: MeldableHeap<u32>>(n: u32) {
+    let mut heap = H::default();
+    for x in 0..n {
+        heap.push(x);
+    }
+    drop(heap);
+
+    let mut heap = H::default();
+    for x in (0..n).rev() {
+        heap.push(x);
+    }
+    drop(heap);
+
+    // The first pop restructures all the elements at once in some heaps.
+    let mut heap = H::default();
+    for x in 0..n {
+        heap.push(x);
+    }
+    assert_eq!(heap.pop(), Some(0));
+    assert_eq!(heap.pop(), Some(1));
+    drop(heap);
+
+    let mut heap = H::default();
+    for x in (0..n).rev() {
+        let mut singleton = H::default();
+        singleton.push(x);
+        heap = H::meld(heap, singleton);
+    }
+    assert_eq!(heap.pop(), Some(0));
+    drop(heap);
+}
+
+fn random_value(rng: &mut SplitMix64, config: &ModelConfig) -> u32 {
+    (rng.next_u64() % config.max_value as u64) as u32
+}
+
+fn sorted(elements: Vec<&u32>) -> Vec<u32> {
+    sorted_values(elements.into_iter().copied().collect())
+}
+
+fn sorted_values(mut values: Vec<u32>) -> Vec<u32> {
+    values.sort_unstable();
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::{check_model, ModelConfig};
+    use crate::AnyHeap;
+
+    #[test]
+    #[should_panic(expected = "seed 0, round 0, step")]
+    fn mismatch_test() {
+        // A heap which forgets the elements melded into it.
+        #[derive(Default)]
+        struct Forgetful(AnyHeap<u32>);
+
+        impl crate::MeldableHeap<u32> for Forgetful {
+            fn is_empty(&self) -> bool {
+                self.0.is_empty()
+            }
+            fn push(&mut self, value: u32) {
+                self.0.push(value)
+            }
+            fn pop(&mut self) -> Option<u32> {
+                self.0.pop()
+            }
+            fn peek(&self) -> Option<&u32> {
+                self.0.peek()
+            }
+            fn meld(heap1: Self, _heap2: Self) -> Self {
+                heap1
+            }
+        }
+
+        impl crate::testing::Inspect<u32> for Forgetful {
+            fn elements(&self) -> alloc::vec::Vec<&u32> {
+                self.0.iter().collect()
+            }
+        }
+
+        check_model::<Forgetful>(&ModelConfig::default());
+    }
+}
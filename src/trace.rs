@@ -0,0 +1,570 @@
+#![forbid(unsafe_code)]
+
+use core::cell::RefCell;
+use core::fmt;
+
+use alloc::vec::Vec;
+
+use crate::{Clock, MeldableHeap};
+
+/// `Recording` wraps a heap and records its operations to a [`Trace`], which can be replayed later
+/// against any heap with [`Trace::replay`].
+///
+/// A recording starts with an empty heap. When two recordings are melded,
+/// the trace of the second one is appended to the trace of the first one as another heap.
+pub struct Recording<H, T> {
+    heap: H,
+    // The index of `heap` in the trace
+    id: u32,
+    // `peek` takes `&self`, so the trace is kept in a `RefCell`.
+    trace: RefCell<Trace<T>>,
+}
+
+impl<T: Clone, H: MeldableHeap<T>> Recording<H, T> {
+    /// Constructs a empty `Recording` with an empty trace.
+    /// O(1) time.
+    pub fn new() -> Self {
+        Self {
+            heap: H::default(),
+            id: 0,
+            trace: RefCell::new(Trace {
+                ops: Vec::new(),
+                heaps: 1,
+            }),
+        }
+    }
+
+    /// Returns `true` if the heap contains no elements.
+    /// This operation is not recorded.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Inserts a value into the heap, and records it with a clone of the value.
+    pub fn push(&mut self, value: T) {
+        let heap = self.id;
+        self.trace.get_mut().ops.push(Op::Push {
+            heap,
+            value: value.clone(),
+        });
+        self.heap.push(value);
+    }
+
+    /// Removes the minimum element from the heap and returns it, or `None` if it is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        let heap = self.id;
+        self.trace.get_mut().ops.push(Op::Pop { heap });
+        self.heap.pop()
+    }
+
+    /// Returns a reference to the minimum element in the heap, or `None` if it is empty.
+    pub fn peek(&self) -> Option<&T> {
+        let heap = self.id;
+        self.trace.borrow_mut().ops.push(Op::Peek { heap });
+        self.heap.peek()
+    }
+
+    /// Melds two heaps into a single heap, and appends the trace of `heap2` to the trace of `heap1`.
+    /// O(m) time in addition to the meld, where m is the length of the trace of `heap2`.
+    pub fn meld(heap1: Recording<H, T>, heap2: Recording<H, T>) -> Recording<H, T> {
+        let mut trace = heap1.trace.into_inner();
+        let other = heap2.trace.into_inner();
+        let offset = trace.heaps;
+        trace.heaps += other.heaps;
+        trace
+            .ops
+            .extend(other.ops.into_iter().map(|op| op.shift(offset)));
+        trace.ops.push(Op::Meld {
+            into: heap1.id,
+            from: heap2.id + offset,
+        });
+        Recording {
+            heap: H::meld(heap1.heap, heap2.heap),
+            id: heap1.id,
+            trace: RefCell::new(trace),
+        }
+    }
+
+    /// Returns a reference to the underlying heap.
+    pub fn heap(&self) -> &H {
+        &self.heap
+    }
+
+    /// Returns a clone of the trace recorded so far.
+    /// O(m) time, where m is the length of the trace.
+    pub fn trace(&self) -> Trace<T> {
+        self.trace.borrow().clone()
+    }
+
+    /// Consumes the recording and returns the trace.
+    pub fn into_trace(self) -> Trace<T> {
+        self.trace.into_inner()
+    }
+}
+
+impl<T: Clone, H: MeldableHeap<T>> Default for Recording<H, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[rustfmt::skip]
+impl<T: Clone, H: MeldableHeap<T>> MeldableHeap<T> for Recording<H, T> {
+    fn is_empty(&self) -> bool { self.is_empty() }
+    fn push(&mut self, value: T) { self.push(value) }
+    fn pop(&mut self) -> Option<T> { self.pop() }
+    fn peek(&self) -> Option<&T> { self.peek() }
+    fn meld(heap1: Self, heap2: Self) -> Self { Self::meld(heap1, heap2) }
+}
+
+/// `Op` is an operation in a [`Trace`]. Heaps are identified by their indices in the trace.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Op<T> {
+    /// Push `value` into `heap`.
+    Push { heap: u32, value: T },
+    /// Pop from `heap`.
+    Pop { heap: u32 },
+    /// Peek at `heap`.
+    Peek { heap: u32 },
+    /// Meld `from` into `into`. `from` becomes empty.
+    Meld { into: u32, from: u32 },
+}
+
+impl<T> Op<T> {
+    fn shift(self, offset: u32) -> Op<T> {
+        match self {
+            Op::Push { heap, value } => Op::Push {
+                heap: heap + offset,
+                value,
+            },
+            Op::Pop { heap } => Op::Pop {
+                heap: heap + offset,
+            },
+            Op::Peek { heap } => Op::Peek {
+                heap: heap + offset,
+            },
+            Op::Meld { into, from } => Op::Meld {
+                into: into + offset,
+                from: from + offset,
+            },
+        }
+    }
+}
+
+/// `Trace` is a sequence of heap operations recorded by [`Recording`].
+///
+/// A trace can be encoded into a compact binary format with [`Trace::encode`] if the values implement [`TraceValue`].
+/// Each operation takes a tag byte and variable-length integers for the heap indices,
+/// followed by the value for a push.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Trace<T> {
+    ops: Vec<Op<T>>,
+    // The number of heaps referred by the operations
+    heaps: u32,
+}
+
+const TAG_PUSH: u8 = 0;
+const TAG_POP: u8 = 1;
+const TAG_PEEK: u8 = 2;
+const TAG_MELD: u8 = 3;
+
+impl<T> Trace<T> {
+    /// Returns the operations in the trace.
+    pub fn ops(&self) -> &[Op<T>] {
+        &self.ops
+    }
+
+    /// Returns the number of heaps in the trace.
+    pub fn heaps(&self) -> u32 {
+        self.heaps
+    }
+
+    /// Runs the operations against heaps of type `H`, and measures the time of each operation with `clock`.
+    /// Cloning the pushed values is not included in the time.
+    pub fn replay<H: MeldableHeap<T>>(&self, mut clock: impl Clock) -> ReplayReport
+    where
+        T: Clone,
+    {
+        let mut heaps: Vec<H> = (0..self.heaps).map(|_| H::default()).collect();
+        let mut report = ReplayReport::default();
+        let start = clock.now();
+        for op in &self.ops {
+            let (timing, elapsed) = match op {
+                Op::Push { heap, value } => {
+                    let value = value.clone();
+                    let start = clock.now();
+                    heaps[*heap as usize].push(value);
+                    (&mut report.pushes, clock.now().saturating_sub(start))
+                }
+                Op::Pop { heap } => {
+                    let start = clock.now();
+                    let value = heaps[*heap as usize].pop();
+                    let elapsed = clock.now().saturating_sub(start);
+                    drop(value);
+                    (&mut report.pops, elapsed)
+                }
+                Op::Peek { heap } => {
+                    let start = clock.now();
+                    let _ = heaps[*heap as usize].peek();
+                    (&mut report.peeks, clock.now().saturating_sub(start))
+                }
+                Op::Meld { into, from } => {
+                    let heap1 = core::mem::take(&mut heaps[*into as usize]);
+                    let heap2 = core::mem::take(&mut heaps[*from as usize]);
+                    let start = clock.now();
+                    heaps[*into as usize] = H::meld(heap1, heap2);
+                    (&mut report.melds, clock.now().saturating_sub(start))
+                }
+            };
+            timing.count += 1;
+            timing.total += elapsed;
+            timing.max = timing.max.max(elapsed);
+        }
+        report.total = clock.now().saturating_sub(start);
+        report
+    }
+}
+
+impl<T: TraceValue> Trace<T> {
+    /// Encodes the trace into the compact binary format.
+    /// O(m) time.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_varint(&mut out, self.heaps as u64);
+        for op in &self.ops {
+            match op {
+                Op::Push { heap, value } => {
+                    out.push(TAG_PUSH);
+                    write_varint(&mut out, *heap as u64);
+                    value.encode(&mut out);
+                }
+                Op::Pop { heap } => {
+                    out.push(TAG_POP);
+                    write_varint(&mut out, *heap as u64);
+                }
+                Op::Peek { heap } => {
+                    out.push(TAG_PEEK);
+                    write_varint(&mut out, *heap as u64);
+                }
+                Op::Meld { into, from } => {
+                    out.push(TAG_MELD);
+                    write_varint(&mut out, *into as u64);
+                    write_varint(&mut out, *from as u64);
+                }
+            }
+        }
+        out
+    }
+
+    /// Decodes a trace encoded by [`Trace::encode`].
+    /// O(m) time.
+    ///
+    /// # Errors
+    /// Returns [`TraceError`] if `bytes` is not a valid trace.
+    pub fn decode(mut bytes: &[u8]) -> Result<Self, TraceError> {
+        let input = &mut bytes;
+        let heaps = read_varint(input).and_then(|heaps| u32::try_from(heaps).ok());
+        let heaps = heaps.ok_or(TraceError::UnexpectedEnd)?;
+        let read_heap = |input: &mut &[u8]| {
+            let heap = read_varint(input).ok_or(TraceError::UnexpectedEnd)?;
+            u32::try_from(heap)
+                .ok()
+                .filter(|&heap| heap < heaps)
+                .ok_or(TraceError::InvalidHeap(heap))
+        };
+
+        let mut ops = Vec::new();
+        while let Some((&tag, rest)) = input.split_first() {
+            *input = rest;
+            let op = match tag {
+                TAG_PUSH => {
+                    let heap = read_heap(input)?;
+                    let value = T::decode(input).ok_or(TraceError::UnexpectedEnd)?;
+                    Op::Push { heap, value }
+                }
+                TAG_POP => Op::Pop {
+                    heap: read_heap(input)?,
+                },
+                TAG_PEEK => Op::Peek {
+                    heap: read_heap(input)?,
+                },
+                TAG_MELD => Op::Meld {
+                    into: read_heap(input)?,
+                    from: read_heap(input)?,
+                },
+                tag => return Err(TraceError::InvalidTag(tag)),
+            };
+            ops.push(op);
+        }
+        Ok(Trace { ops, heaps })
+    }
+}
+
+/// `ReplayReport` is the result of [`Trace::replay`]. The unit of time is that of the clock.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct ReplayReport {
+    /// The timing of `push`.
+    pub pushes: OpTiming,
+    /// The timing of `pop`.
+    pub pops: OpTiming,
+    /// The timing of `peek`.
+    pub peeks: OpTiming,
+    /// The timing of `meld`.
+    pub melds: OpTiming,
+    /// The time of the whole replay.
+    pub total: u64,
+}
+
+/// `OpTiming` is the timing of a kind of operation in [`ReplayReport`].
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct OpTiming {
+    /// The number of operations.
+    pub count: u64,
+    /// The total time of the operations.
+    pub total: u64,
+    /// The maximum time of an operation.
+    pub max: u64,
+}
+
+/// The error returned by [`Trace::decode`] when the input is not a valid trace.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TraceError {
+    /// The input ends in the middle of an operation.
+    UnexpectedEnd,
+    /// The input contains an unknown operation tag.
+    InvalidTag(u8),
+    /// An operation refers to a heap which is not in the trace.
+    InvalidHeap(u64),
+}
+
+impl fmt::Display for TraceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TraceError::UnexpectedEnd => f.write_str("unexpected end of trace"),
+            TraceError::InvalidTag(tag) => write!(f, "invalid operation tag {tag}"),
+            TraceError::InvalidHeap(heap) => write!(f, "invalid heap index {heap}"),
+        }
+    }
+}
+
+impl core::error::Error for TraceError {}
+
+/// `TraceValue` is a value which can be encoded in the binary format of [`Trace`].
+pub trait TraceValue: Sized {
+    /// Appends the encoding of the value to `out`.
+    fn encode(&self, out: &mut Vec<u8>);
+
+    /// Decodes a value from the head of `input` and advances it, or returns `None` if the input is invalid.
+    fn decode(input: &mut &[u8]) -> Option<Self>;
+}
+
+// Unsigned integers are encoded in LEB128, so that small values take few bytes.
+macro_rules! impl_trace_value_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl TraceValue for $t {
+                fn encode(&self, out: &mut Vec<u8>) {
+                    write_varint(out, *self as u64);
+                }
+
+                fn decode(input: &mut &[u8]) -> Option<Self> {
+                    read_varint(input).and_then(|x| <$t>::try_from(x).ok())
+                }
+            }
+        )*
+    };
+}
+
+impl_trace_value_unsigned!(u8, u16, u32, u64, usize);
+
+// Signed integers are zigzag-encoded, so that small negative values also take few bytes.
+macro_rules! impl_trace_value_signed {
+    ($($t:ty),*) => {
+        $(
+            impl TraceValue for $t {
+                fn encode(&self, out: &mut Vec<u8>) {
+                    let x = *self as i64;
+                    write_varint(out, ((x << 1) ^ (x >> 63)) as u64);
+                }
+
+                fn decode(input: &mut &[u8]) -> Option<Self> {
+                    let x = read_varint(input)?;
+                    let x = (x >> 1) as i64 ^ -((x & 1) as i64);
+                    <$t>::try_from(x).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_trace_value_signed!(i8, i16, i32, i64, isize);
+
+impl TraceValue for () {
+    fn encode(&self, _out: &mut Vec<u8>) {}
+
+    fn decode(_input: &mut &[u8]) -> Option<Self> {
+        Some(())
+    }
+}
+
+impl<A: TraceValue, B: TraceValue> TraceValue for (A, B) {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.0.encode(out);
+        self.1.encode(out);
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        Some((A::decode(input)?, B::decode(input)?))
+    }
+}
+
+impl<T: TraceValue, const N: usize> TraceValue for [T; N] {
+    fn encode(&self, out: &mut Vec<u8>) {
+        for x in self {
+            x.encode(out);
+        }
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        let values = (0..N)
+            .map(|_| T::decode(input))
+            .collect::<Option<Vec<_>>>()?;
+        values.try_into().ok()
+    }
+}
+
+fn write_varint(out: &mut Vec<u8>, mut x: u64) {
+    while x >= 0x80 {
+        out.push(x as u8 | 0x80);
+        x >>= 7;
+    }
+    out.push(x as u8);
+}
+
+fn read_varint(input: &mut &[u8]) -> Option<u64> {
+    let mut x = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = input.split_first()?;
+        *input = rest;
+        x |= ((byte & 0x7f) as u64).checked_shl(shift)?;
+        if byte < 0x80 {
+            return Some(x);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use crate::{LeftistHeap, NoClock, Op, PairingHeap, Recording, SkewHeap, Trace, TraceError};
+
+    #[test]
+    fn record_test() {
+        let mut heap1 = Recording::<PairingHeap<_>, _>::new();
+        heap1.push(3);
+        heap1.push(1);
+        assert_eq!(heap1.peek(), Some(&1));
+        let mut heap2 = Recording::<PairingHeap<_>, _>::new();
+        heap2.push(2);
+        let mut heap = Recording::meld(heap1, heap2);
+        assert_eq!(heap.pop(), Some(1));
+
+        let trace = heap.into_trace();
+        assert_eq!(trace.heaps(), 2);
+        let expected = vec![
+            Op::Push { heap: 0, value: 3 },
+            Op::Push { heap: 0, value: 1 },
+            Op::Peek { heap: 0 },
+            Op::Push { heap: 1, value: 2 },
+            Op::Meld { into: 0, from: 1 },
+            Op::Pop { heap: 0 },
+        ];
+        assert_eq!(trace.ops(), expected);
+    }
+
+    #[test]
+    fn encode_test() {
+        let mut heap = Recording::<LeftistHeap<_>, _>::new();
+        for x in [300u64, 0, u64::MAX] {
+            heap.push((x, -1i32));
+        }
+        heap.pop();
+        let mut other = Recording::<LeftistHeap<_>, _>::new();
+        other.push((1, 1));
+        let heap = Recording::meld(heap, other);
+        let trace = heap.into_trace();
+
+        let bytes = trace.encode();
+        assert_eq!(Trace::decode(&bytes), Ok(trace));
+
+        let mut heap = Recording::<SkewHeap<_>, _>::new();
+        heap.push([i64::MIN, 0, i64::MAX]);
+        heap.push([-1, 1, -64]);
+        let trace = heap.into_trace();
+        assert_eq!(Trace::decode(&trace.encode()), Ok(trace));
+
+        // heaps = 1, push to heap 0 of (1, 0)
+        assert_eq!(
+            Trace::<(u8, u8)>::decode(&[1, 0, 0, 1, 0])
+                .unwrap()
+                .ops()
+                .len(),
+            1
+        );
+        assert_eq!(
+            Trace::<u8>::decode(&[1, 0, 0]),
+            Err(TraceError::UnexpectedEnd)
+        );
+        assert_eq!(
+            Trace::<u8>::decode(&[1, 4, 0]),
+            Err(TraceError::InvalidTag(4))
+        );
+        assert_eq!(
+            Trace::<u8>::decode(&[1, 1, 1]),
+            Err(TraceError::InvalidHeap(1))
+        );
+        assert_eq!(
+            Trace::<u8>::decode(&[1, 0, 0, 200]),
+            Err(TraceError::UnexpectedEnd)
+        );
+    }
+
+    #[test]
+    fn replay_test() {
+        let mut heap = Recording::<PairingHeap<_>, _>::new();
+        let mut popped = Vec::new();
+        for i in 0..1000u32 {
+            heap.push(rand::random::<u32>() % 100);
+            if i % 3 == 0 {
+                popped.push(heap.pop());
+            }
+            if i % 100 == 0 {
+                let mut other = Recording::new();
+                other.push(i);
+                heap = Recording::meld(heap, other);
+            }
+        }
+        let trace = heap.trace();
+
+        // The replay against another heap performs the same operations.
+        let mut time = 0;
+        let clock = || {
+            time += 1;
+            time
+        };
+        let report = trace.replay::<SkewHeap<_>>(clock);
+        assert_eq!(report.pushes.count, 1010);
+        assert_eq!(report.pops.count, popped.len() as u64);
+        assert_eq!(report.melds.count, 10);
+        assert_eq!(report.pops.total, report.pops.count);
+        assert_eq!(report.pops.max, 1);
+        assert!(report.total > 2 * 1010);
+
+        let report = trace.replay::<LeftistHeap<_>>(NoClock);
+        assert_eq!(report.pushes.count, 1010);
+        assert_eq!(report.total, 0);
+    }
+}
@@ -0,0 +1,237 @@
+#![forbid(unsafe_code)]
+
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::MeldableHeap;
+
+/// `WeightBiasedLeftistHeap` is a priority queue implemented with weight-biased leftist heap.
+/// `WeightBiasedLeftistHeap` is a **min-heap**, which means that the minimum element is popped first.
+///
+/// Each node stores the size of its subtree instead of the rank of `LeftistHeap`.
+/// Since the size of the melded subtree is known in advance, `meld` can maintain the leftist property
+/// in a single top-down pass, and `len` takes O(1) time.
+///
+/// # Reference
+/// Cho, Seonghun; Sahni, Sartaj (1998).
+/// ["Weight-Biased Leftist Trees and Modified Skip Lists"][Cho-Sahni-1998]
+///
+/// [Cho-Sahni-1998]: https://doi.org/10.1145/297096.297111
+pub struct WeightBiasedLeftistHeap<T: Ord> {
+    root: Option<Box<Node<T>>>,
+}
+
+impl<T: Ord> WeightBiasedLeftistHeap<T> {
+    /// Constructs a empty `WeightBiasedLeftistHeap`.
+    /// O(1) time.
+    pub fn new() -> Self {
+        Self { root: None }
+    }
+
+    /// Returns `true` if the heap contains no elements.
+    /// O(1) time.
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Returns the number of elements in the heap.
+    /// O(1) time.
+    pub fn len(&self) -> usize {
+        self.root.as_ref().map_or(0, |node| node.size)
+    }
+
+    /// Inserts a value into the heap.
+    /// O(log n) time.
+    pub fn push(&mut self, value: T) {
+        self.root = Node::meld(self.root.take(), Node::singleton(value));
+    }
+
+    /// Removes the minimum element from the heap and returns it, or `None` if it is empty.
+    /// O(log n) time.
+    pub fn pop(&mut self) -> Option<T> {
+        let root = self.root.take()?;
+        self.root = Node::meld(root.left, root.right);
+        Some(root.value)
+    }
+
+    /// Returns a reference to the minimum element in the heap, or `None` if it is empty.
+    /// O(1) time.
+    pub fn peek(&self) -> Option<&T> {
+        self.root.as_ref().map(|node| &node.value)
+    }
+
+    /// Melds two heaps into a single heap.
+    /// O(log n) time.
+    pub fn meld(
+        mut heap1: WeightBiasedLeftistHeap<T>,
+        mut heap2: WeightBiasedLeftistHeap<T>,
+    ) -> WeightBiasedLeftistHeap<T> {
+        let root = Node::meld(heap1.root.take(), heap2.root.take());
+        Self { root }
+    }
+
+    /// Returns an iterator that visits all elements in the heap, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let mut stack = Vec::new();
+        if let Some(ref root) = self.root {
+            stack.push(root.as_ref());
+        }
+        Iter { stack }
+    }
+}
+
+impl<T: Ord> Default for WeightBiasedLeftistHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[rustfmt::skip]
+impl<T: Ord> MeldableHeap<T> for WeightBiasedLeftistHeap<T> {
+    fn is_empty(&self) -> bool { self.is_empty() }
+    fn push(&mut self, value: T) { self.push(value) }
+    fn pop(&mut self) -> Option<T> { self.pop() }
+    fn peek(&self) -> Option<&T> { self.peek() }
+    fn meld(heap1: Self, heap2: Self) -> Self { Self::meld(heap1, heap2) }
+}
+
+// We need to implement `drop` for WeightBiasedLeftistHeap because auto-generated `drop` would cause stack overflow
+// (the depth of the tree can be O(n) in the worst case).
+impl<T: Ord> Drop for WeightBiasedLeftistHeap<T> {
+    // Visit all nodes in depth-first order, and drop them one-by-one.
+    //
+    // This implementation reuses heap nodes to create a stack structure.
+    // Therefore, it consumes only O(1) memory except for the heap itself.
+    fn drop(&mut self) {
+        let mut stack_top = None;
+        let mut opt_node = self.root.take();
+
+        loop {
+            while let Some(mut node) = opt_node {
+                let left = node.left;
+
+                // push node to the stack
+                node.left = stack_top;
+                stack_top = Some(node);
+
+                // move to the left child
+                opt_node = left;
+            }
+
+            // pop a node from the stack
+            let Some(top) = stack_top else { break };
+            stack_top = top.left;
+            opt_node = top.right;
+
+            // `top` is deallocated here
+        }
+    }
+}
+
+struct Node<T: Ord> {
+    value: T,
+    size: usize,
+    left: Option<Box<Node<T>>>,
+    right: Option<Box<Node<T>>>,
+}
+
+impl<T: Ord> Node<T> {
+    fn singleton(value: T) -> Option<Box<Node<T>>> {
+        Some(Box::new(Self {
+            value,
+            size: 1,
+            left: None,
+            right: None,
+        }))
+    }
+
+    fn size(node: &Option<Box<Node<T>>>) -> usize {
+        node.as_ref().map_or(0, |node| node.size)
+    }
+
+    fn meld(root1: Option<Box<Node<T>>>, root2: Option<Box<Node<T>>>) -> Option<Box<Node<T>>> {
+        let (mut root1, mut root2) = match (root1, root2) {
+            (None, root2) => return root2,
+            (root1, None) => return root1,
+            (Some(r1), Some(r2)) => (r1, r2),
+        };
+
+        if root1.value > root2.value {
+            core::mem::swap(&mut root1, &mut root2);
+        }
+        let mut new_root = root1;
+
+        // initialize loop variables
+        let mut parent = &mut new_root;
+        let mut node2 = root2;
+
+        // `node2` is melded into the right subtree of `parent` in each iteration.
+        loop {
+            parent.size += node2.size;
+            let node1_opt = parent.right.take();
+
+            // The size of the melded subtree is known before melding,
+            // so we can decide which side it goes to maintain the leftist property.
+            let slot = if Self::size(&parent.left) < Self::size(&node1_opt) + node2.size {
+                parent.right = parent.left.take();
+                &mut parent.left
+            } else {
+                &mut parent.right
+            };
+
+            let Some(mut node1) = node1_opt else {
+                *slot = Some(node2);
+                break;
+            };
+            if node1.value > node2.value {
+                core::mem::swap(&mut node1, &mut node2);
+            }
+            parent = slot.insert(node1);
+        }
+
+        Some(new_root)
+    }
+}
+
+struct Iter<'a, T: Ord> {
+    stack: Vec<&'a Node<T>>,
+}
+
+impl<'a, T: Ord> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        if let Some(left) = &node.left {
+            self.stack.push(left);
+        }
+        if let Some(right) = &node.right {
+            self.stack.push(right);
+        }
+        Some(&node.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::{self, ModelConfig};
+    use crate::WeightBiasedLeftistHeap;
+
+    #[test]
+    fn basic_test() {
+        testing::check_basic::<WeightBiasedLeftistHeap<u32>>();
+    }
+
+    #[test]
+    fn large_drop_test() {
+        testing::check_deep::<WeightBiasedLeftistHeap<u32>>(1000000);
+    }
+
+    #[test]
+    fn randomized_test() {
+        let config = ModelConfig {
+            seed: rand::random(),
+            ..ModelConfig::default()
+        };
+        testing::check_model::<WeightBiasedLeftistHeap<u32>>(&config);
+    }
+}